@@ -6,7 +6,7 @@ PacletObject[<|
 	"Extensions" -> {
 		{"Kernel", "Root" -> "Source/WolframCLI", "Context" -> "ConnorGray`WolframCLI`"},
 		{"Kernel", "Root" -> "Source/ClapLink", "Context" -> "ConnorGray`ClapLink`"},
-		{"LibraryResources"}
+		{"LibraryResources", "CargoManifest" -> "../crates/clap-link/Cargo.toml"}
 	},
 	"Dependencies" -> {
 		"Wolfram/ErrorTools"
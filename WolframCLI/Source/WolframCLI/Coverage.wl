@@ -0,0 +1,495 @@
+BeginPackage["ConnorGray`WolframCLI`Coverage`"]
+
+WithCoverage::usage = "WithCoverage[files, expr] evaluates expr, causing loads of the specified source files during the evaluation to record which definitions and lines are evaluated."
+
+PrintCoverageSummary::usage = "PrintCoverageSummary[] prints a summary of the coverage recorded for each instrumented source file."
+
+WriteCoverageLCOV::usage = "WriteCoverageLCOV[file] writes the recorded coverage data to file in the lcov tracefile format."
+
+Begin["`Private`"]
+
+Needs["ConnorGray`WolframCLI`"]
+Needs["ConnorGray`WolframCLI`Errors`"]
+
+(*
+	Instrumentation works by parsing each source file with CodeParser, and
+	inserting a call to coverageHit[id] at the start of:
+
+	  * the right-hand side of every delayed definition (`:=`, `^:=`, `/: :=`)
+	  * every statement in a CompoundExpression inside such a definition
+
+	Each id is an index into $coverageRegistry, which records the source
+	location that a coverageHit[id] call corresponds to.
+
+	Top-level code outside of delayed definitions, which is evaluated when the
+	file is loaded, is not instrumented, so it isn't included in the recorded
+	line coverage.
+*)
+
+(* Absolute paths of the source files to instrument. *)
+$coverageFiles = {}
+
+(* Association of name -> the source file found for `name` by
+   findSourceFile[name]. *)
+$sourceFiles = <||>
+
+(* Source files that have already been loaded with instrumentation. *)
+$instrumentedLoaded = {}
+
+(* List of associations of the form
+   <| "File" -> _, "Line" -> _, "Kind" -> "Line" | "Definition", "Name" -> _ |>. *)
+$coverageRegistry = {}
+
+(* Association of id -> number of times that coverageHit[id] was evaluated. *)
+$coverageCounts = <||>
+
+$delayedDefinitionHeads = "SetDelayed" | "TagSetDelayed" | "UpSetDelayed"
+
+(*====================================*)
+
+SetFallthroughError[WithCoverage]
+
+SetAttributes[WithCoverage, HoldRest]
+
+WithCoverage[{}, body_] := body
+
+(*
+	The definitions intercepting Get and Needs only exist during the
+	evaluation of `body`.
+*)
+WithCoverage[files: {__?StringQ}, body_] := Block[{
+	$coverageFiles = Map[ExpandFileName, files],
+	$sourceFiles = <||>
+},
+	Internal`InheritedBlock[{Get, Needs},
+		Unprotect[Needs, Get];
+
+		(* Intercept loading the instrumented files by context name. *)
+		Needs[arg:(ctx_?StringQ | (ctx_?StringQ -> _)), rest___] /; (
+			!MemberQ[$Packages, ctx]
+			&& instrumentedFileQ[findSourceFile[ctx]]
+			&& !MemberQ[$instrumentedLoaded, findSourceFile[ctx]]
+		) := (
+			loadInstrumented[findSourceFile[ctx]];
+			Needs[arg, rest]
+		);
+
+		(* Intercept loading the instrumented files by context name or path. *)
+		Get[name_?StringQ] /; instrumentedFileQ[findSourceFile[name]] :=
+			loadInstrumented[findSourceFile[name]];
+
+		Protect[Needs, Get];
+
+		body
+	]
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[instrumentedFileQ]
+
+instrumentedFileQ[file_] := StringQ[file] && MemberQ[$coverageFiles, file]
+
+(*------------------------------------*)
+
+(* The source files are memoized, so that the FindFile[..] lookup is done
+   only once for each file or context loaded during the test run. *)
+SetFallthroughError[findSourceFile]
+
+findSourceFile[name_?StringQ] := If[KeyExistsQ[$sourceFiles, name],
+	$sourceFiles[name],
+	$sourceFiles[name] = Replace[FindFile[name], {
+		file_?StringQ :> ExpandFileName[file],
+		_ :> None
+	}]
+]
+
+(*====================================*)
+
+coverageHit[id_Integer] := (
+	$coverageCounts[id] = Lookup[$coverageCounts, id, 0] + 1;
+)
+
+(*------------------------------------*)
+
+SetFallthroughError[registerCoverageId]
+
+registerCoverageId[file_?StringQ, line_Integer, kind_?StringQ, name_] := (
+	AppendTo[$coverageRegistry, <|
+		"File" -> file,
+		"Line" -> line,
+		"Kind" -> kind,
+		"Name" -> name
+	|>];
+
+	Length[$coverageRegistry]
+)
+
+(*====================================*)
+
+(*
+	Evaluate each top-level expression in `file`, like Get[file], but with
+	delayed definitions instrumented to record coverage.
+*)
+SetFallthroughError[loadInstrumented]
+
+loadInstrumented[file_?StringQ] := Module[{
+	text,
+	concrete,
+	chunks
+},
+	Needs["CodeParser`" -> None];
+
+	AppendTo[$instrumentedLoaded, file];
+
+	text = ReadString[file];
+
+	concrete = CodeParser`CodeConcreteParse[
+		text,
+		"SourceConvention" -> "SourceCharacterIndex"
+	];
+
+	(* Fall back to loading the file normally if it has syntax errors. *)
+	If[!FreeQ[concrete, _CodeParser`ErrorNode | _CodeParser`SyntaxErrorNode],
+		Print[TerminalStyle[
+			"warning: unable to instrument source file with syntax errors: " <> file,
+			"Yellow"
+		]];
+		Return[Block[{$coverageFiles = DeleteCases[$coverageFiles, file]},
+			Get[file]
+		], Module]
+	];
+
+	(* The source text of each top-level expression, and the line it
+	   starts on. *)
+	chunks = Cases[
+		concrete[[2]],
+		node_ /; !MatchQ[node, CodeParser`LeafNode[
+			Token`Newline | Token`Comment | Whitespace | Token`Whitespace,
+			_,
+			_
+		]] :> Replace[node[[3]], {
+			KeyValuePattern[CodeParser`Source -> {start_, end_}] :> {
+				StringTake[text, {start, end}],
+				StringCount[StringTake[text, start], "\n"] + 1
+			}
+		}]
+	];
+
+	Block[{$InputFileName = file},
+		Scan[
+			Apply[{chunk, line} |-> evaluateChunk[file, chunk, line]],
+			chunks
+		]
+	];
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[evaluateChunk]
+
+evaluateChunk[file_?StringQ, chunk_?StringQ, startLine_Integer] := Module[{
+	node
+},
+	node = Replace[CodeParser`CodeParse[chunk], {
+		CodeParser`ContainerNode[String, {node0_}, _] :> node0,
+		other_ :> Raise[
+			WolframCLIError,
+			"unexpected result parsing source file chunk: ``",
+			InputForm[other]
+		]
+	}];
+
+	(* Only chunks that contain delayed definitions need to be
+	   instrumented. *)
+	If[FreeQ[node, CodeParser`LeafNode[Symbol, $delayedDefinitionHeads, _]],
+		ToExpression[chunk]
+		,
+		ToExpression[CodeParser`ToFullFormString[
+			instrumentTopLevel[file, startLine - 1, node]
+		]]
+	]
+]
+
+(*------------------------------------*)
+
+(* Instrument the definitions in a top-level expression, or in each
+   statement of a top-level CompoundExpression. *)
+SetFallthroughError[instrumentTopLevel]
+
+instrumentTopLevel[file_, lineOffset_, node_] := Replace[node, {
+	CodeParser`CallNode[
+		head:CodeParser`LeafNode[Symbol, "CompoundExpression", _],
+		children_List,
+		data_
+	] :> CodeParser`CallNode[
+		head,
+		Map[child |-> instrumentTopLevel[file, lineOffset, child], children],
+		data
+	],
+	CodeParser`CallNode[
+		head:CodeParser`LeafNode[Symbol, $delayedDefinitionHeads, _],
+		{lhs__, rhs_},
+		data_
+	] :> Module[{id},
+		id = registerCoverageId[
+			file,
+			nodeLine[node, lineOffset],
+			"Definition",
+			definitionName[{lhs}, nodeLine[node, lineOffset]]
+		];
+
+		CodeParser`CallNode[
+			head,
+			{lhs, Replace[rhs, {
+				(* Condition[..] must remain the outermost expression on the
+				   right-hand side of the definition. *)
+				CodeParser`CallNode[
+					conditionHead:CodeParser`LeafNode[Symbol, "Condition", _],
+					{body_, condition_},
+					conditionData_
+				] :> CodeParser`CallNode[
+					conditionHead,
+					{
+						compoundNode[{hitNode[id], instrumentRHS[file, lineOffset, body]}],
+						condition
+					},
+					conditionData
+				],
+				_ :> compoundNode[{hitNode[id], instrumentRHS[file, lineOffset, rhs]}]
+			}]},
+			data
+		]
+	],
+	other_ :> other
+}]
+
+(*------------------------------------*)
+
+(* Instrument the right-hand side of a definition, recording a line hit at
+   the start of the body if it isn't already recorded by the first statement
+   of a CompoundExpression. *)
+SetFallthroughError[instrumentRHS]
+
+instrumentRHS[file_, lineOffset_, rhs_] := Replace[rhs, {
+	CodeParser`CallNode[CodeParser`LeafNode[Symbol, "CompoundExpression", _], _, _] :>
+		instrumentBody[file, lineOffset, rhs],
+	_ :> Replace[nodeLine[rhs, lineOffset], {
+		line_Integer :> compoundNode[{
+			hitNode[registerCoverageId[file, line, "Line", None]],
+			instrumentBody[file, lineOffset, rhs]
+		}],
+		None :> instrumentBody[file, lineOffset, rhs]
+	}]
+}]
+
+(*------------------------------------*)
+
+(* Instrument each statement in a definition body. *)
+SetFallthroughError[instrumentBody]
+
+instrumentBody[file_, lineOffset_, node_] := Replace[node, {
+	CodeParser`CallNode[
+		head:CodeParser`LeafNode[Symbol, "CompoundExpression", _],
+		children_List,
+		data_
+	] :> CodeParser`CallNode[
+		head,
+		Flatten[Map[
+			child |-> Replace[{child, nodeLine[child, lineOffset]}, {
+				(* Don't count the implicit Null after a trailing `;` as a
+				   separate line. *)
+				{CodeParser`LeafNode[Symbol, "Null", _], _} :> child,
+				{_, line_Integer} :> {
+					hitNode[registerCoverageId[file, line, "Line", None]],
+					instrumentBody[file, lineOffset, child]
+				},
+				{_, None} :> instrumentBody[file, lineOffset, child]
+			}],
+			children
+		], 1],
+		data
+	],
+	CodeParser`CallNode[head_, children_List, data_] :> CodeParser`CallNode[
+		instrumentBody[file, lineOffset, head],
+		Map[child |-> instrumentBody[file, lineOffset, child], children],
+		data
+	],
+	other_ :> other
+}]
+
+(*------------------------------------*)
+
+SetFallthroughError[nodeLine]
+
+nodeLine[node_, lineOffset_Integer] := Replace[node, {
+	_[_, _, KeyValuePattern[CodeParser`Source -> {{line_Integer, _}, _}]] :> line + lineOffset,
+	_ :> None
+}]
+
+(*------------------------------------*)
+
+SetFallthroughError[definitionName]
+
+definitionName[lhs_List, line_] := Replace[
+	FirstCase[lhs, CodeParser`LeafNode[Symbol, name_?StringQ, _] :> name, None, Infinity],
+	{
+		name_?StringQ :> name <> ":" <> ToString[line],
+		None :> "definition:" <> ToString[line]
+	}
+]
+
+(*------------------------------------*)
+
+hitNode[id_Integer] := CodeParser`CallNode[
+	CodeParser`LeafNode[Symbol, "ConnorGray`WolframCLI`Coverage`Private`coverageHit", <||>],
+	{CodeParser`LeafNode[Integer, ToString[id], <||>]},
+	<||>
+]
+
+compoundNode[children_List] := CodeParser`CallNode[
+	CodeParser`LeafNode[Symbol, "CompoundExpression", <||>],
+	children,
+	<||>
+]
+
+(*====================================*)
+
+(* Returns an association of the form
+   <| file -> <| "Lines" -> <| line -> count |>, "Definitions" -> {{name, line, count}...} |> |>. *)
+SetFallthroughError[coverageByFile]
+
+coverageByFile[] := Module[{
+	entries
+},
+	entries = MapIndexed[
+		{entry, index} |-> Append[entry, "Count" -> Lookup[$coverageCounts, First[index], 0]],
+		$coverageRegistry
+	];
+
+	Map[
+		fileEntries |-> <|
+			"Lines" -> KeySort @ Merge[
+				Map[
+					entry |-> entry["Line"] -> entry["Count"],
+					Select[fileEntries, #Kind === "Line" &]
+				],
+				Max
+			],
+			"Definitions" -> Map[
+				entry |-> {entry["Name"], entry["Line"], entry["Count"]},
+				Select[fileEntries, #Kind === "Definition" &]
+			]
+		|>,
+		GroupBy[entries, #File &]
+	]
+]
+
+(*====================================*)
+
+SetFallthroughError[PrintCoverageSummary]
+
+PrintCoverageSummary[] := Module[{
+	coverage = coverageByFile[]
+},
+	Print[];
+	Print[TerminalStyle["Coverage:", Bold, Underlined]];
+	Print[];
+
+	If[coverage === <||>,
+		Print["\tNo instrumented source files were loaded during the test run."];
+		Return[Null, Module];
+	];
+
+	KeyValueMap[
+		{file, data} |-> Module[{
+			lines = Values[data["Lines"]],
+			definitions = data["Definitions"][[All, 3]]
+		},
+			Print[
+				"\t",
+				formatPercent[Count[lines, _?Positive], Length[lines]],
+				" lines, ",
+				formatPercent[Count[definitions, _?Positive], Length[definitions]],
+				" definitions",
+				"\t",
+				file
+			]
+		],
+		coverage
+	];
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[formatPercent]
+
+formatPercent[hit_Integer, total_Integer] := Module[{
+	percent = If[total === 0, 100., N[100 * hit / total]]
+},
+	StringJoin[
+		TerminalStyle[
+			ToString[NumberForm[percent, {Infinity, 1}]] <> "%",
+			Which[
+				percent >= 80, "Green",
+				percent >= 50, "Yellow",
+				True, "Red"
+			]
+		],
+		" (", ToString[hit], "/", ToString[total], ")"
+	]
+]
+
+(*====================================*)
+
+SetFallthroughError[WriteCoverageLCOV]
+
+WriteCoverageLCOV[outputFile_?StringQ] := Module[{
+	records
+},
+	records = KeyValueMap[
+		{file, data} |-> Module[{
+			lines = data["Lines"],
+			definitions = data["Definitions"]
+		},
+			StringJoin[
+				"TN:\n",
+				"SF:", file, "\n",
+				Map[
+					Apply[{name, line, count} |-> StringJoin[
+						"FN:", ToString[line], ",", name, "\n"
+					]],
+					definitions
+				],
+				Map[
+					Apply[{name, line, count} |-> StringJoin[
+						"FNDA:", ToString[count], ",", name, "\n"
+					]],
+					definitions
+				],
+				"FNF:", ToString[Length[definitions]], "\n",
+				"FNH:", ToString[Count[definitions[[All, 3]], _?Positive]], "\n",
+				KeyValueMap[
+					{line, count} |-> StringJoin[
+						"DA:", ToString[line], ",", ToString[count], "\n"
+					],
+					lines
+				],
+				"LF:", ToString[Length[lines]], "\n",
+				"LH:", ToString[Count[Values[lines], _?Positive]], "\n",
+				"end_of_record\n"
+			]
+		],
+		coverageByFile[]
+	];
+
+	If[!DirectoryQ[FileNameDrop[outputFile]],
+		CreateDirectory[FileNameDrop[outputFile], CreateIntermediateDirectories -> True];
+	];
+
+	Export[outputFile, StringJoin[records], "Text"]
+]
+
+End[]
+
+EndPackage[]
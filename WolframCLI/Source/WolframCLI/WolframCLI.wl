@@ -16,10 +16,15 @@ TerminalForm::usage = "TerminalForm[expr] prints as a character-terminal represe
 (* Built-in command handler functions *)
 (*------------------------------------*)
 
+CommandPacletBench::usage = "Handle the command `$ wolfram paclet bench`."
 CommandPacletBuild::usage = "Handle the command `$ wolfram paclet build`."
 CommandPacletDoc::usage = "Handle the command `$ wolfram paclet doc`."
 CommandPacletInstall::usage = "Handle the command `$ wolfram paclet install`."
+CommandPacletList::usage = "Handle the command `$ wolfram paclet list`."
 CommandPacletTest::usage = "Handle the command `$ wolfram paclet test`."
+CommandPacletUninstall::usage = "Handle the command `$ wolfram paclet uninstall`."
+
+CommandTest::usage = "Handle the command `$ wolfram test`."
 
 CommandHandleCustom::usage = "Handle custom subcommands defined by \"WolframCLI\" paclet extensions."
 
@@ -30,6 +35,7 @@ Begin["`Private`"]
 Needs["ConnorGray`WolframCLI`"]
 Needs["ConnorGray`WolframCLI`Errors`"]
 Needs["ConnorGray`WolframCLI`TerminalForm`"]
+Needs["ConnorGray`WolframCLI`Coverage`"]
 
 (* FIXME: Remove this automatic initialization. *)
 LoadTerminalForm[]
@@ -42,21 +48,146 @@ Options[CommandPacletTest] = {
 		Maximum lines of context to include around textual diffs shown in
 		failed test results.
 	*)
-	"DiffContext" -> 10
+	"DiffContext" -> 10,
+	(*
+		How to show the differences between the expected and actual values of
+		failed tests:
+
+		* "Text" prints a textual diff.
+		* "Event" sends the formatted values to the client as a "TestDiff"
+		  event, to be rendered by the client.
+		* None doesn't show any diff.
+	*)
+	"DiffStyle" -> "Text",
+	(*
+		Test files to run before any other test files.
+	*)
+	"PrioritizedFiles" -> {},
+	(*
+		Whether to launch a replacement testing subkernel once this test run
+		is finished, to be used by the next call to CommandPacletTest.
+	*)
+	"WarmKernel" -> False,
+	(*
+		Whether to run only the tests that did not succeed in the previous
+		test run.
+	*)
+	"RerunFailed" -> False,
+	(*
+		Whether to run the test files containing tests that did not succeed
+		in the previous test run before any other test files.
+	*)
+	"FailedFirst" -> False,
+	(*
+		Number of slowest tests to list after the test run summary, or None.
+	*)
+	"Durations" -> None,
+	(*
+		Whether to record which definitions and lines in the paclet Kernel
+		sources are evaluated by the tests.
+	*)
+	"Coverage" -> False,
+	(*
+		Whether to rewrite the expected output of failing snapshot tests
+		in the test source files with their actual output.
+
+		Snapshot tests are tests whose TestID starts with "Snapshot:".
+	*)
+	"UpdateSnapshots" -> False,
+	(*
+		Whether to disable installed copies of the paclet that would
+		otherwise take precedence over the paclet in the paclet directory
+		during the test run.
+
+		The tests are run in a subkernel with a temporary user base
+		directory, so that disabling paclets doesn't affect any other
+		kernel, even if the subkernel exits unexpectedly.
+	*)
+	"DisableInstalled" -> False,
+	(*
+		Whether to run the tests in a subkernel launched with an empty,
+		temporary user base directory and without running init.m files.
+	*)
+	"Isolated" -> False,
+	(*
+		Directories to add to $Path and load with PacletDirectoryLoad before
+		the tests are run, to make the packages they contain available to the
+		tests.
+	*)
+	"LoadDirectories" -> {},
+	(*
+		Whether to run the test files, and the tests within each file when
+		that is safe, in a random order.
+	*)
+	"Shuffle" -> False,
+	(*
+		Seed determining the random order of tests, or Automatic to choose
+		a random seed.
+	*)
+	"ShuffleSeed" -> Automatic,
+	(*
+		Whether to send "RunStarted", "FileStarted", and "TestFinished"
+		events to the client as the tests run, so that the client can display
+		the progress of the test run.
+	*)
+	"ProgressEvents" -> False
 }
 
-(* Handle `$ wolfram paclet test` *)
+(*
+	Handle `$ wolfram paclet test`
+
+	If `pacletDir` is None, the test files or directories in `testsPath` are
+	run without loading any paclet.
+*)
 CommandPacletTest[
-	pacletDir: _?StringQ,
-	testsPath: _?StringQ | Automatic : Automatic,
+	pacletDir: _?StringQ | None,
+	testsPath: _?StringQ | {___?StringQ} | Automatic : Automatic,
 	OptionsPattern[]
 ] := With[{
-	diffContext = RaiseConfirmMatch[OptionValue["DiffContext"], _?IntegerQ]
+	diffContext = RaiseConfirmMatch[OptionValue["DiffContext"], _?IntegerQ],
+	diffStyle = RaiseConfirmMatch[OptionValue["DiffStyle"], "Text" | "Event" | None],
+	prioritizedFiles = RaiseConfirmMatch[
+		OptionValue["PrioritizedFiles"],
+		{___?StringQ}
+	],
+	warmKernel = RaiseConfirmMatch[OptionValue["WarmKernel"], _?BooleanQ],
+	rerunFailed = RaiseConfirmMatch[OptionValue["RerunFailed"], _?BooleanQ],
+	failedFirst = RaiseConfirmMatch[OptionValue["FailedFirst"], _?BooleanQ],
+	durations = RaiseConfirmMatch[OptionValue["Durations"], _?IntegerQ | None],
+	coverage = RaiseConfirmMatch[OptionValue["Coverage"], _?BooleanQ],
+	updateSnapshots = RaiseConfirmMatch[OptionValue["UpdateSnapshots"], _?BooleanQ],
+	disableInstalled = RaiseConfirmMatch[OptionValue["DisableInstalled"], _?BooleanQ],
+	isolated = RaiseConfirmMatch[OptionValue["Isolated"], _?BooleanQ],
+	loadDirs = Map[
+		ExpandFileName,
+		RaiseConfirmMatch[OptionValue["LoadDirectories"], {___?StringQ}]
+	],
+	(* Directory in which the results of test runs are recorded. *)
+	resultsDir = Replace[pacletDir, None :> Directory[]],
+	shuffle = RaiseConfirmMatch[OptionValue["Shuffle"], _?BooleanQ],
+	shuffleSeed = Replace[
+		RaiseConfirmMatch[OptionValue["ShuffleSeed"], _?IntegerQ | Automatic],
+		Automatic :> RandomInteger[{0, 2^31 - 1}]
+	],
+	progressEvents = RaiseConfirmMatch[OptionValue["ProgressEvents"], _?BooleanQ]
 },
 Module[{
 	result,
-	linkObj
+	linkObj,
+	failedTests,
+	testResults = None
 },
+	If[pacletDir === None,
+		RaiseAssert[
+			testsPath =!= Automatic,
+			"tests to run must be specified when no paclet directory is specified"
+		];
+		RaiseAssert[
+			!coverage,
+			"coverage can only be recorded for tests of a paclet"
+		];
+	];
+
 (*
 	Needs["PacletTools`" -> None];
 
@@ -112,6 +243,29 @@ Module[{
 		support to PacletTest for passing through EventHandlers for reacting to
 		testing events as they happen. *)
 
+	(*--------------------------------------------------------*)
+	(* Look up the tests that failed in the previous test run *)
+	(*--------------------------------------------------------*)
+
+	(* Association of the form <| file -> {testKey...} |>. *)
+	failedTests = Select[
+		Map[
+			fileResults |-> Keys @ Select[
+				fileResults,
+				result |-> !MatchQ[result["Outcome"], "Success" | "SnapshotUpdated"]
+			],
+			readTestResultsCache[resultsDir]
+		],
+		keys |-> keys =!= {}
+	];
+
+	If[rerunFailed && failedTests === <||>,
+		Print[TerminalStyle[
+			"info: No failed tests were recorded by a previous test run. Running all tests.",
+			"Gray"
+		]];
+	];
+
 	(*-------------------------------------------------*)
 	(* Launch a fresh subkernel for running the tests. *)
 	(*-------------------------------------------------*)
@@ -131,21 +285,37 @@ Module[{
 			TODO: Only launch this subkernel if the specified paclet is one
 				loaded by WolframCLI`.
 	*)
-	linkObj = LinkLaunch[First[$CommandLine] <> " -wstp"];
-
-	MathLink`LinkSetPrintFullSymbols[linkObj, True];
-
-	LinkRead[linkObj]; (* Read the InputNamePacket. *)
+	linkObj = takeTestKernel[isolated, disableInstalled];
 
 	(*--------------------------------------------------------------------*)
 	(* Send an expression to drive the test run to the testing subkernel. *)
 	(*--------------------------------------------------------------------*)
 
+	(* Inline the value of `failedTests`, `wolframCLIDirs`, and
+	   `installedDirs` into the expression sent to the testing subkernel. *)
+	With[{
+		failedTests = failedTests,
+		wolframCLIDirs = If[isolated, wolframCLIPacletDirectories[], {}],
+		installedDirs = If[disableInstalled && !isolated,
+			installedPacletDirectories[pacletDir],
+			{}
+		]
+	},
 	LinkWrite[linkObj, Unevaluated @ EvaluatePacket @ Module[{
 		testsDirs,
 		testFiles,
 		summaryData,
-		logger
+		logger,
+		currentFile,
+		results = {},
+		fileStatistics = {},
+		statistics,
+		setupFiles,
+		teardownFiles,
+		fixtureError,
+		snapshotUpdates,
+		shadowing,
+		runFile
 	},
 		(* Prevent the testing subkernel from adding "\" and ">" characters from
 		   wrapping long lines. *)
@@ -155,20 +325,71 @@ Module[{
 			Do PacletDirectoryLoad[pacletDir] first to ensure that the
 			specified paclet directory is the paclet actually loaded during
 			the test run.
-
-			TODO:
-				This can still work incorrectly if the installed version
-				of a paclet has a higher version number than the paclet
-				in `pacletDir`. Check for that scenario and issue an error
-				or at least a warning. (Suggest they do PacletDisable, or
-				instead temporarily do that ourselves?)
 		*)
-		PacletDirectoryLoad[pacletDir];
+		If[StringQ[pacletDir],
+			PacletDirectoryLoad[pacletDir];
+		];
+
+		Scan[
+			dir |-> (
+				PacletDirectoryLoad[dir];
+				PrependTo[$Path, dir];
+			),
+			loadDirs
+		];
+
+		(* An isolated subkernel has an empty user base directory, so make
+		   the installed copy of WolframCLI` available, and install only the
+		   dependencies declared by the paclet. *)
+		If[isolated,
+			Scan[PacletDirectoryLoad, wolframCLIDirs];
+			If[StringQ[pacletDir],
+				PacletInstall /@ PacletObject[File[pacletDir]]["Dependencies"];
+			];
+		];
+
+		(* A subkernel launched to disable installed paclets has a temporary
+		   user base directory, so make the paclets installed in the actual
+		   user base available, except for copies of the tested paclet. *)
+		Scan[PacletDirectoryLoad, installedDirs];
 
 		Needs["PacletTools`" -> None];
 		Needs["MUnit`" -> None];
 		Needs["ConnorGray`WolframCLI`" -> None];
 
+		(*
+			PacletDirectoryLoad[pacletDir] isn't sufficient if an installed
+			copy of the paclet has a higher version number than the paclet in
+			`pacletDir`. In that case, the tests would silently run against the
+			installed copy.
+		*)
+		shadowing = If[StringQ[pacletDir], shadowingPaclets[pacletDir], {}];
+
+		If[shadowing =!= {},
+			If[disableInstalled,
+				(* Disabling a paclet is persistent, but only affects the
+				   temporary user base directory of this subkernel. *)
+				Print[TerminalStyle[
+					"info: Disabling installed copies of the paclet for this test run: ",
+					"Gray"
+				]];
+				Scan[
+					paclet |-> Print[TerminalStyle["\t" <> paclet["Location"], "Gray"]],
+					shadowing
+				];
+				Scan[PacletDisable, shadowing];
+				,
+				printShadowingError[pacletDir, shadowing];
+				Return[
+					Failure["PacletShadowed", <|
+						"MessageTemplate" -> "Paclet in `` is shadowed by an installed paclet.",
+						"MessageParameters" -> {pacletDir}
+					|>],
+					Module
+				];
+			];
+		];
+
 		testFiles = Replace[testsPath, {
 			Automatic | _?DirectoryQ :> (
 				testsDirs = Replace[testsPath, {
@@ -192,10 +413,17 @@ Module[{
 				]
 			),
 			_ /; FileType[testsPath] === File :> {testsPath},
+			paths:{___?StringQ} :> Flatten @ Map[
+				path |-> If[DirectoryQ[path],
+					FileNames["*.mt" | "*.wlt", path],
+					path
+				],
+				paths
+			],
 			other_ :> (
 				Raise[
 					WolframCLIError,
-					"invalid testsPath value: must be a file, directory, or Automatic: ``",
+					"invalid testsPath value: must be a file, directory, list of files and directories, or Automatic: ``",
 					InputForm[testsPath]
 				]
 			)
@@ -203,17 +431,85 @@ Module[{
 
 		RaiseAssert[MatchQ[testFiles, {___?StringQ}]];
 
+		testFiles = Map[ExpandFileName, testFiles];
+
+		If[rerunFailed && failedTests =!= <||>,
+			testFiles = Select[testFiles, file |-> KeyExistsQ[failedTests, file]];
+		];
+
+		(* Use BlockRandom so that shuffling doesn't affect the random state
+		   seen by the tests. *)
+		If[shuffle,
+			testFiles = BlockRandom[
+				RandomSample[testFiles],
+				RandomSeeding -> shuffleSeed
+			];
+		];
+
+		(* Run any prioritized test files first, preserving the relative order
+		   of all other test files. *)
+		With[{
+			prioritizedQ = file |-> MemberQ[
+				ExpandFileName /@ Join[
+					prioritizedFiles,
+					If[failedFirst, Keys[failedTests], {}]
+				],
+				file
+			]
+		},
+			testFiles = Join[
+				Select[testFiles, prioritizedQ],
+				Select[testFiles, Not @* prioritizedQ]
+			]
+		];
+
+		If[progressEvents,
+			printClientEvent["RunStarted", <|
+				"FileCount" -> Length[testFiles],
+				"TestCount" -> Total @ Map[
+					file |-> If[rerunFailed && failedTests =!= <||>,
+						Length[Lookup[failedTests, file, {}]],
+						testCount[file]
+					],
+					testFiles
+				]
+			|>];
+		];
+
 		summaryData = <|
 			"Success" -> 0,
 			"Failure" -> 0,
 			"MessagesFailure" -> 0,
-			"Error" -> 0
+			"Error" -> 0,
+			"SnapshotUpdated" -> 0,
+			"FixtureError" -> 0
 		|>;
 
-		logger = Function[testResult,
-			summaryData[testResult["Outcome"]] += 1;
-			printTestResult[testResult, diffContext];
-		];
+		logger = Function[testResult, Module[{
+			outcome = If[updateSnapshots && snapshotTestQ[testResult] && testResult["Outcome"] === "Failure",
+				"SnapshotUpdated",
+				testResult["Outcome"]
+			]
+		},
+			summaryData[outcome] += 1;
+			If[outcome === "SnapshotUpdated",
+				AppendTo[snapshotUpdates, testResult];
+			];
+			AppendTo[results, <|
+				"File" -> currentFile,
+				"TestKey" -> Replace[
+					$currentTestKey,
+					Except[_?StringQ] :> ToString[testResult["TestID"]]
+				],
+				"Outcome" -> outcome,
+				"AbsoluteTimeUsed" -> quantityValue[testResult["AbsoluteTimeUsed"], "Seconds"],
+				"MemoryUsed" -> quantityValue[testResult["MemoryUsed"], "Bytes"]
+			|>];
+			printTestResult[testResult, diffContext, diffStyle];
+			If[progressEvents,
+				printClientEvent["TestFinished", <| "Outcome" -> outcome |>];
+			];
+		]];
 
 		logger = <|
 			"LogSuccess" -> logger,
@@ -228,12 +524,71 @@ Module[{
 		(* Run each testing file, logging test results *)
 		(*---------------------------------------------*)
 
-		Scan[
-			file |-> (
-				Print[TerminalStyle["FILE:", Bold, Underlined], " ", file];
-				MUnit`TestRun[file, "Loggers" -> {logger}];
-			),
-			testFiles
+		(* Loads of the paclet source files during the test run are instrumented
+		   to record coverage. *)
+		WithCoverage[
+			If[coverage, pacletSourceFiles[pacletDir], {}],
+			Scan[
+				file |-> (
+					Print[TerminalStyle["FILE:", Bold, Underlined], " ", file];
+					If[progressEvents,
+						printClientEvent["FileStarted", <| "File" -> file |>];
+					];
+					currentFile = file;
+					snapshotUpdates = {};
+					runFile = If[shuffle,
+						Replace[shuffledTestFile[file, shuffleSeed], None -> file],
+						file
+					];
+					{setupFiles, teardownFiles} = testFixtureFiles[file];
+					fixtureError = False;
+					(* The teardown fixtures are run even if the setup fixtures or
+					   the tests fail, or the test run is aborted. *)
+					statistics = evaluateWithFileStatistics @ WithCleanup[
+						fixtureError = !AllTrue[setupFiles, runTestFixture]
+						,
+						If[!fixtureError,
+							withTestSelection[
+								If[rerunFailed && failedTests =!= <||>,
+									key |-> MemberQ[Lookup[failedTests, file, {}], key],
+									key |-> True
+								],
+								MUnit`TestRun[runFile, "Loggers" -> {logger}]
+							]
+						]
+						,
+						If[!AllTrue[Map[runTestFixture, teardownFiles], TrueQ],
+							fixtureError = True;
+						];
+					];
+					If[fixtureError,
+						summaryData["FixtureError"] += 1;
+					];
+					printFileStatistics[statistics];
+					AppendTo[fileStatistics, Join[
+						<|
+							"File" -> file,
+							"FixtureError" -> fixtureError,
+							"TestCount" -> Count[results, KeyValuePattern["File" -> file]],
+							"FailureCount" -> Count[
+								results,
+								KeyValuePattern[{
+									"File" -> file,
+									"Outcome" -> Except["Success" | "SnapshotUpdated"]
+								}]
+							]
+						|>,
+						statistics
+					]];
+					If[runFile =!= file,
+						DeleteDirectory[DirectoryName[runFile], DeleteContents -> True];
+					];
+					If[snapshotUpdates =!= {},
+						updateSnapshotTests[file, snapshotUpdates];
+					];
+				),
+				testFiles
+			]
 		];
 
 		(*---------------------------------*)
@@ -250,6 +605,7 @@ Module[{
 					"Failure" -> "Red",
 					"MessagesFailure" -> "Yellow",
 					"Error" -> "Red",
+					"SnapshotUpdated" -> "Blue",
 					_ -> None
 				}];
 			];
@@ -276,14 +632,193 @@ Module[{
 		printSummaryDatapoint["Failure", "failed"];
 		printSummaryDatapoint["MessagesFailure", "had unexpected message output"];
 		printSummaryDatapoint["Error", "produced unexpected errors"];
-	]];
+
+		If[updateSnapshots,
+			printSummaryDatapoint["SnapshotUpdated", "had their snapshot updated"];
+		];
+
+		If[summaryData["FixtureError"] > 0,
+			Print[
+				"\t",
+				TerminalStyle[summaryData["FixtureError"], "Red"],
+				" test ",
+				Pluralize[{"file", "files"}, summaryData["FixtureError"]],
+				" had setup or teardown errors"
+			];
+		];
+
+		If[shuffle,
+			Print[];
+			Print["Tests were run in a random order using seed: ", TerminalStyle[shuffleSeed, Bold]];
+			Print[TerminalStyle[
+				"hint: Use `--shuffle --seed " <> ToString[shuffleSeed] <> "` to run the tests in the same order.",
+				"Gray"
+			]];
+		];
+
+		If[IntegerQ[durations],
+			printSlowestTests[results, durations];
+		];
+
+		If[coverage,
+			PrintCoverageSummary[];
+			Print[];
+			Print[
+				"Wrote coverage data to: ",
+				WriteCoverageLCOV[
+					FileNameJoin[{pacletDir, "build", "wolfram-cli", "lcov.info"}]
+				]
+			];
+		];
+
+		<| "Results" -> results, "Files" -> fileStatistics |>
+	]]
+	];
 
 	(*-------------------------------------------------*)
 	(* Process packets sent from the testing subkernel *)
 	(*-------------------------------------------------*)
 
-	(* Re-print output sent from the testing subkernel, and close the link once
-	   the testing evaluation returns. *)
+	testResults = forwardSubkernelOutput[linkObj];
+
+	closeSubkernel[linkObj];
+
+	If[MatchQ[testResults, KeyValuePattern[{
+		"Results" -> {___?AssociationQ},
+		"Files" -> {___?AssociationQ}
+	}]],
+		writeTestResultsCache[resultsDir, testResults["Results"]];
+		writeTestFileReport[resultsDir, testResults["Files"]];
+	];
+
+	If[warmKernel,
+		$warmTestKernel = launchSubkernel[isolated, disableInstalled];
+	];
+
+	(* Return a summary of the test run to the client. *)
+	Replace[testResults, {
+		KeyValuePattern[{
+			"Results" -> results:{___?AssociationQ},
+			"Files" -> files:{___?AssociationQ}
+		}] :> <|
+			"TestCount" -> Length[results],
+			(* Tests whose snapshots were updated are not failures. Test files
+			   whose fixtures failed count as a single failure. *)
+			"FailureCount" -> Count[
+				results,
+				KeyValuePattern["Outcome" -> Except["Success" | "SnapshotUpdated"]]
+			] + Count[files, KeyValuePattern["FixtureError" -> True]]
+		|>,
+		other_ :> other
+	}]
+]]
+
+(*------------------------------------*)
+
+(* Handle `$ wolfram test` *)
+CommandTest[
+	testsPaths: {___?StringQ},
+	opts:OptionsPattern[CommandPacletTest]
+] :=
+	CommandPacletTest[None, testsPaths, opts]
+
+(*------------------------------------*)
+
+(* A testing subkernel launched ahead of time by a previous call to
+   CommandPacletTest[.., "WarmKernel" -> True]. *)
+$warmTestKernel = None
+
+(* Temporary user base directories of subkernels launched by
+   launchSubkernel[..], keyed by the subkernel link. *)
+$subkernelUserBases = <||>
+
+(*
+	Launch a subkernel, without waiting for it to finish starting up.
+
+	If `isolated` is True, the subkernel is launched with a fresh, temporary
+	user base directory and without running init.m files, so that paclets
+	installed by the user and other persistent state can't affect it.
+
+	If `temporaryUserBase` is True, the subkernel is launched with a fresh,
+	temporary user base directory, so that persistent changes it makes to
+	paclets (like PacletDisable[..]) don't affect any other kernel.
+*)
+SetFallthroughError[launchSubkernel]
+
+launchSubkernel[
+	isolated_?BooleanQ,
+	temporaryUserBase_?BooleanQ : False
+] := Module[{
+	linkObj,
+	userBase,
+	previousUserBase
+},
+	If[isolated || temporaryUserBase,
+		userBase = CreateDirectory[];
+		previousUserBase = Environment["WOLFRAM_USERBASE"];
+
+		(* The subkernel inherits the environment of this kernel. *)
+		linkObj = WithCleanup[
+			SetEnvironment["WOLFRAM_USERBASE" -> userBase],
+			LinkLaunch[First[$CommandLine] <> If[isolated, " -wstp -noinit", " -wstp"]],
+			SetEnvironment[
+				"WOLFRAM_USERBASE" -> Replace[previousUserBase, $Failed -> None]
+			]
+		];
+
+		$subkernelUserBases[linkObj] = userBase;
+		,
+		linkObj = LinkLaunch[First[$CommandLine] <> " -wstp"];
+	];
+
+	MathLink`LinkSetPrintFullSymbols[linkObj, True];
+
+	linkObj
+]
+
+(* Close a subkernel launched by launchSubkernel[..], and delete its
+   temporary user base directory, if any. *)
+SetFallthroughError[closeSubkernel]
+
+closeSubkernel[linkObj_LinkObject] := (
+	LinkClose[linkObj];
+
+	Replace[Lookup[$subkernelUserBases, linkObj, None], {
+		userBase_?StringQ :> (
+			DeleteDirectory[userBase, DeleteContents -> True];
+			KeyDropFrom[$subkernelUserBases, linkObj];
+		),
+		None :> Null
+	}];
+)
+
+(* Get a testing subkernel that is ready to evaluate input, reusing the warm
+   kernel if there is one. *)
+SetFallthroughError[takeTestKernel]
+
+takeTestKernel[
+	isolated_?BooleanQ,
+	temporaryUserBase_?BooleanQ
+] := Module[{linkObj},
+	linkObj = Replace[$warmTestKernel, {
+		None :> launchSubkernel[isolated, temporaryUserBase],
+		link_LinkObject :> link
+	}];
+
+	$warmTestKernel = None;
+
+	LinkRead[linkObj]; (* Read the InputNamePacket. *)
+
+	linkObj
+]
+
+(*
+	Re-print output sent from a subkernel until the evaluation sent to it
+	returns, and return the result of that evaluation.
+*)
+SetFallthroughError[forwardSubkernelOutput]
+
+forwardSubkernelOutput[linkObj_LinkObject] := Module[{result = None},
 	While[True,
 		Replace[LinkRead[linkObj], {
 			packet:(TextPacket[output_?StringQ] | MessagePacket[__]) :> (
@@ -292,7 +827,7 @@ Module[{
 						wolfram-cli functionality is moved into WolframKernel,
 						where there isn't a parent link. *)
 					Throw[Row[{
-						"Error forwarding packet from `paclet test` subkernel: $ParentLink is not _LinkObject: ",
+						"Error forwarding packet from subkernel: $ParentLink is not _LinkObject: ",
 						InputForm[$ParentLink]
 					}]]
 				];
@@ -303,99 +838,782 @@ Module[{
 				LinkWrite[$ParentLink, packet];
 			),
 			ReturnPacket[expr_] :> (
-				(* TODO: Do something with `expr`, like print testing summary
-					results? *)
-				LinkClose[linkObj];
+				result = expr;
 				Break[]
 			),
 			other_ :> (
-				Print["Unexpected packet sent from Kernel during test run: ", InputForm[other]];
-				LinkClose[linkObj];
+				Print["Unexpected packet sent from subkernel: ", InputForm[other]];
 				Break[]
 			)
 		}];
 	];
-]]
+
+	result
+]
+
+(* The directories of the installed WolframCLI paclet and the paclets it
+   depends on, for loading into an isolated subkernel. *)
+SetFallthroughError[wolframCLIPacletDirectories]
+
+wolframCLIPacletDirectories[] := Module[{paclet = PacletObject["ConnorGray/WolframCLI"]},
+	RaiseConfirmMatch[
+		Map[
+			dependency |-> dependency["Location"],
+			Prepend[Map[PacletObject, paclet["Dependencies"]], paclet]
+		],
+		{___?StringQ}
+	]
+]
 
 (*------------------------------------*)
 
-SetFallthroughError[printTestResult]
+(*
+	Write a copy of the test file `file` with its tests in a random order
+	determined by `seed`, and return the path of the copy, or None if the tests
+	in `file` can't safely be reordered.
+
+	Tests are only reordered if `file` contains nothing but tests that have a
+	TestID: other top-level expressions (like definitions) may be needed by the
+	tests that follow them, and tests without a TestID are identified by their
+	position in the file. Files that refer to $InputFileName or $TestFileName
+	are also not reordered, because the copy is written to a temporary
+	directory.
+*)
+SetFallthroughError[shuffledTestFile]
 
-printTestResult[test_TestResultObject, diffContext_?IntegerQ] := Module[{},
-	Replace[test["Outcome"], {
-		"Success" :> Print[Format[test, TerminalForm]],
-		"Failure" :> Module[{
-			formattedInput,
-			a,
-			b
-		},
-			formattedInput = formattedTestField[test, "Input"];
+shuffledTestFile[file_?StringQ, seed_Integer] := Module[{
+	text,
+	nodes,
+	copy,
+	stream
+},
+	Needs["CodeParser`" -> None];
 
-			Print[
-				Format[test, TerminalForm],
-				" -- ",
-				TerminalStyle["expected", "Red", Italic],
-				" | ",
-				TerminalStyle["actual", "Green", Italic]
-			];
+	text = ReadString[file];
 
-			Print[
-				"| ", TerminalStyle["Input:", Underlined], " ", TerminalStyle[formattedInput, "Blue"]
-			];
+	If[StringContainsQ[text, "$InputFileName" | "$TestFileName"],
+		Return[None, Module];
+	];
 
-			(* Strip the HoldForm wrapper -- this shouldn't be necessary in almost all cases. *)
-			a = Replace[test["ExpectedOutput"], HoldForm[x_] :> x];
-			b = Replace[test["ActualOutput"], HoldForm[x_] :> x];
+	nodes = Replace[
+		CodeParser`CodeParse[text, "SourceConvention" -> "SourceCharacterIndex"],
+		{
+			CodeParser`ContainerNode[_, nodes_List, _] :> nodes,
+			_ :> Return[None, Module]
+		}
+	];
 
-			printTextualExprDiff[a, b, diffContext]
-		],
-		"Error" :> Module[{},
-			Print[Format[test, TerminalForm]];
-			Replace[test[All], {
-				KeyValuePattern[{
-					"Input" -> HoldForm[input0_],
-					"ActualOutput" -> Hold[Throw[payload_, ___]]
-				}] :> Module[{
-					payloadString = ToString[Unevaluated @ payload, InputForm],
-					formattedInput
-				},
-					formattedInput = formattedTestField[test, "Input"];
+	If[Length[nodes] < 2 || !AllTrue[nodes, testWithIDNodeQ],
+		Return[None, Module];
+	];
 
-					Print[
-						"| ", TerminalStyle["Input:", Underlined], " ", TerminalStyle[formattedInput, "Blue"]
-					];
-					Print[
-						TerminalStyle["Unexpected Exception: ", "Red"],
-						payloadString
+	copy = FileNameJoin[{CreateDirectory[], FileNameTake[file]}];
+
+	WithCleanup[
+		stream = OpenWrite[copy],
+		WriteString[
+			stream,
+			StringRiffle[
+				Map[
+					node |-> StringTake[text, node[[3, Key[CodeParser`Source]]]],
+					BlockRandom[
+						RandomSample[nodes],
+						RandomSeeding -> {seed, FileNameTake[file]}
 					]
 				],
-				other_ :> (
-					Print["Unhandled 'Error' test object format: ", InputForm[other]]
-				)
-			}];
+				"\n\n"
+			]
 		],
-		"MessagesFailure" :> Module[{
-			formattedInput
-		},
-			formattedInput = formattedTestField[test, "Input"];
+		Close[stream]
+	];
 
-			Print[
-				Format[test, TerminalForm],
-				" -- ",
-				TerminalStyle["expected", "Red", Italic],
-				" | ",
-				TerminalStyle["actual", "Green", Italic]
-			];
+	copy
+]
 
-			Print[
-				"| ", TerminalStyle["Input:", Underlined], " ", TerminalStyle[formattedInput, "Blue"]
-			];
+(*------------------------------------*)
 
-			(* Strip the HoldForm wrapper -- this shouldn't be necessary in almost all cases. *)
-			a = Replace[test["ExpectedMessages"], HoldForm[x_] :> x];
+(*
+	Returns the setup and teardown fixture files of the test file `file`, in
+	the form {setupFiles, teardownFiles}.
+
+	The fixture files are the Setup.wl and Teardown.wl files in the directory
+	containing `file`, if they exist, and any files named by fixture markers
+	in `file`, which are comments of the form:
+
+		(* :Setup: path *)
+		(* :Teardown: path *)
+
+	where `path` is relative to the directory containing `file`. Setup.wl is
+	run before the setup files named in `file`, and Teardown.wl is run after
+	the teardown files named in `file`.
+*)
+SetFallthroughError[testFixtureFiles]
+
+testFixtureFiles[file_?StringQ] := Module[{
+	dir = DirectoryName[file],
+	text = ReadString[file],
+	markedFiles
+},
+	markedFiles = kind |-> StringCases[
+		text,
+		StartOfLine ~~ "(* :" ~~ kind ~~ ": " ~~ path:Shortest[Except["\n"]..] ~~ " *)" :>
+			FileNameJoin[{dir, StringTrim[path]}]
+	];
+
+	{
+		Join[
+			Select[{FileNameJoin[{dir, "Setup.wl"}]}, FileExistsQ],
+			markedFiles["Setup"]
+		],
+		Join[
+			markedFiles["Teardown"],
+			Select[{FileNameJoin[{dir, "Teardown.wl"}]}, FileExistsQ]
+		]
+	}
+]
+
+(*------------------------------------*)
+
+(* Run the test fixture file `file`, returning False and printing an error
+   if it doesn't exist, issues messages, or returns a failure. *)
+SetFallthroughError[runTestFixture]
+
+runTestFixture[file_?StringQ] := Module[{result},
+	result = Check[Get[file], $Failed];
+
+	If[FailureQ[result],
+		Print[
+			TerminalStyle["FIXTURE ERROR:", "Red", Bold],
+			" ",
+			file,
+			" ",
+			TerminalStyle["(" <> ToString[result, InputForm] <> ")", "Gray"]
+		];
+		Return[False, Module];
+	];
+
+	True
+]
+
+(*------------------------------------*)
+
+(*
+	Returns the number of tests in the test file `file`.
+
+	This is the number of VerificationTest[..] and Test[..] expressions in
+	the file, which won't match the number of tests actually run if the file
+	generates tests programmatically.
+*)
+SetFallthroughError[testCount]
+
+testCount[file_?StringQ] := (
+	Needs["CodeParser`" -> None];
+
+	Length @ Cases[
+		CodeParser`CodeParse[File[file]],
+		CodeParser`CallNode[
+			CodeParser`LeafNode[Symbol, "VerificationTest" | "Test" | "MUnit`Test", _],
+			_,
+			_
+		],
+		Infinity
+	]
+)
+
+(*------------------------------------*)
+
+SetFallthroughError[testWithIDNodeQ]
+
+testWithIDNodeQ[node_] := MatchQ[
+	node,
+	CodeParser`CallNode[
+		CodeParser`LeafNode[Symbol, "VerificationTest" | "Test" | "MUnit`Test", _],
+		{
+			___,
+			CodeParser`CallNode[
+				CodeParser`LeafNode[Symbol, "Rule", _],
+				{CodeParser`LeafNode[Symbol, "TestID", _], _},
+				_
+			],
+			___
+		},
+		_
+	]
+]
+
+(*------------------------------------*)
+
+(* The key identifying the test currently being evaluated by
+   withTestSelection[..]. *)
+$currentTestKey = None
+
+(*
+	Evaluate `body`, modifying VerificationTest and MUnit`Test so that only
+	the tests whose key satisfies `selectedQ` are evaluated.
+
+	The key of a test is its TestID, or "#n" if it is the n-th test in `body`
+	and doesn't have a TestID.
+*)
+SetFallthroughError[withTestSelection]
+
+SetAttributes[withTestSelection, HoldRest]
+
+withTestSelection[selectedQ_, body_] := Module[{
+	index = 0,
+	inside = False,
+	savedDownValues
+},
+	WithCleanup[
+		savedDownValues = {DownValues[VerificationTest], DownValues[MUnit`Test]};
+
+		Scan[
+			Function[testHead,
+				Unprotect[testHead];
+
+				testHead[args___] /; !inside := Block[{inside = True},
+					index += 1;
+					$currentTestKey = testKey[index, Hold[args]];
+
+					If[TrueQ[selectedQ[$currentTestKey]],
+						testHead[args],
+						Null
+					]
+				];
+
+				Protect[testHead];
+			],
+			{VerificationTest, MUnit`Test}
+		];
+		,
+		body
+		,
+		Unprotect[VerificationTest, MUnit`Test];
+		DownValues[VerificationTest] = savedDownValues[[1]];
+		DownValues[MUnit`Test] = savedDownValues[[2]];
+		Protect[VerificationTest, MUnit`Test];
+		$currentTestKey = None;
+	]
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[testKey]
+
+testKey[index_Integer, held_Hold] := Replace[
+	Cases[held, HoldPattern[TestID -> id_?StringQ] :> id],
+	{
+		{id_, ___} :> id,
+		{} :> "#" <> ToString[index]
+	}
+]
+
+(*------------------------------------*)
+
+(*
+	The test results cache records the outcome, timing, and memory use of each
+	test in the most recent run of a test file, in the form:
+
+		<| dir -> <| testFile -> <| testKey -> <|
+			"Outcome" -> outcome,
+			"AbsoluteTimeUsed" -> seconds,
+			"MemoryUsed" -> bytes
+		|> |> |> |>
+
+	where `dir` is the tested paclet directory, or the working directory of
+	a `$ wolfram test` run.
+*)
+SetFallthroughError[testResultsCacheFile]
+
+testResultsCacheFile[dir_?StringQ] :=
+	FileNameJoin[{dir, "build", "wolfram-cli", "test-results.json"}]
+
+(*------------------------------------*)
+
+(* Returns the results recorded for `dir`, in the form
+   <| testFile -> <| testKey -> <| "Outcome" -> outcome, ... |> |> |>. *)
+SetFallthroughError[readTestResultsCache]
+
+readTestResultsCache[dir_?StringQ] := Module[{
+	file = testResultsCacheFile[dir],
+	cache
+},
+	If[!FileExistsQ[file],
+		Return[<||>, Module];
+	];
+
+	cache = Import[file, "RawJSON"];
+
+	If[!AssociationQ[cache],
+		Print[TerminalStyle[
+			"warning: Ignoring malformed test results cache file: " <> file,
+			"Yellow"
+		]];
+		Return[<||>, Module];
+	];
+
+	Replace[Lookup[cache, ExpandFileName[dir], <||>], {
+		results:<| (_?StringQ -> <| (_?StringQ -> KeyValuePattern[{
+			"Outcome" -> _?StringQ
+		}]) ... |>) ... |> :> results,
+		_ :> <||>
+	}]
+]
+
+(*------------------------------------*)
+
+(* Record the outcome of each test in `results`, replacing any previously
+   recorded outcomes for the test files that were run. *)
+SetFallthroughError[writeTestResultsCache]
+
+writeTestResultsCache[
+	dir_?StringQ,
+	results: {___?AssociationQ}
+] := Module[{
+	file = testResultsCacheFile[dir],
+	cache,
+	pacletResults
+},
+	cache = If[FileExistsQ[file], Import[file, "RawJSON"], <||>];
+
+	If[!AssociationQ[cache],
+		cache = <||>;
+	];
+
+	pacletResults = Join[
+		readTestResultsCache[dir],
+		Map[
+			fileResults |-> Association @ Map[
+				result |-> result["TestKey"] -> KeyDrop[result, {"File", "TestKey"}],
+				fileResults
+			],
+			GroupBy[results, #File &]
+		]
+	];
+
+	cache[ExpandFileName[dir]] = pacletResults;
+
+	If[!DirectoryQ[FileNameDrop[file]],
+		CreateDirectory[FileNameDrop[file], CreateIntermediateDirectories -> True];
+	];
+
+	Export[file, cache, "RawJSON"];
+]
+
+(*------------------------------------*)
+
+(* Returns the paclets that take precedence over the paclet in `pacletDir`
+   when resolving the paclet by name. *)
+SetFallthroughError[shadowingPaclets]
+
+shadowingPaclets[pacletDir_?StringQ] := Module[{
+	paclet = PacletObject[File[pacletDir]],
+	location
+},
+	RaiseAssert[
+		MatchQ[paclet, _PacletObject],
+		"unable to read paclet in directory: ``",
+		pacletDir
+	];
+
+	location = ExpandFileName[paclet["Location"]];
+
+	(* PacletFind returns paclets in the order of precedence used to resolve
+	   a paclet name. *)
+	TakeWhile[
+		PacletFind[paclet["Name"]],
+		other |-> ExpandFileName[other["Location"]] =!= location
+	]
+]
+
+(*------------------------------------*)
+
+(* Returns the directories of the paclets installed in the user base directory
+   of this kernel, except for copies of the paclet in `pacletDir`, for loading
+   into a subkernel with a temporary user base directory. *)
+SetFallthroughError[installedPacletDirectories]
+
+installedPacletDirectories[pacletDir: _?StringQ | None] := Module[{
+	name = If[StringQ[pacletDir], PacletObject[File[pacletDir]]["Name"], None],
+	userBasePaclets = ExpandFileName[$UserBasePacletsDirectory]
+},
+	Map[
+		paclet |-> paclet["Location"],
+		Select[
+			PacletFind[All],
+			paclet |-> And[
+				paclet["Name"] =!= name,
+				StringStartsQ[ExpandFileName[paclet["Location"]], userBasePaclets]
+			]
+		]
+	]
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[printShadowingError]
+
+printShadowingError[pacletDir_?StringQ, shadowing: {__PacletObject}] := Module[{
+	paclet = PacletObject[File[pacletDir]]
+},
+	Print[
+		TerminalStyle["error:", "Red", Bold],
+		" The paclet ",
+		paclet["Name"],
+		" in ",
+		pacletDir,
+		" (version ",
+		paclet["Version"],
+		") is shadowed by ",
+		Pluralize[{"an installed copy", "installed copies"}, Length[shadowing]],
+		" of the same paclet:"
+	];
+	Print[];
+	Scan[
+		other |-> Print["\t", other["Name"], " ", other["Version"], "\t", other["Location"]],
+		shadowing
+	];
+	Print[];
+	Print[
+		"Tests would run against the installed code instead of the code in the paclet directory."
+	];
+	Print[TerminalStyle[
+		"hint: Use `--disable-installed` to disable the installed copies during the test run, or uninstall them with `wolfram-cli paclet uninstall`.",
+		"Gray"
+	]];
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[snapshotTestQ]
+
+snapshotTestQ[test_TestResultObject] :=
+	StringQ[test["TestID"]] && StringStartsQ[test["TestID"], "Snapshot:"]
+
+(*------------------------------------*)
+
+(*
+	Replace the expected output of each of the snapshot `tests` in the source
+	of `file` with the actual output of the test.
+
+	Only the source text of the expected output argument is modified, so the
+	formatting of the rest of the file is preserved.
+*)
+SetFallthroughError[updateSnapshotTests]
+
+updateSnapshotTests[file_?StringQ, tests: {___TestResultObject}] := Module[{
+	text,
+	ast,
+	expectedSources,
+	replacements,
+	stream
+},
+	Needs["CodeParser`" -> None];
+
+	text = ReadString[file];
+
+	ast = CodeParser`CodeParse[text, "SourceConvention" -> "SourceCharacterIndex"];
+
+	(* Association of TestID -> {start, end} character span of the expected
+	   output argument of each snapshot test in `file`. *)
+	expectedSources = Association @ Cases[
+		ast,
+		CodeParser`CallNode[
+			CodeParser`LeafNode[Symbol, "VerificationTest", _],
+			{
+				_,
+				CodeParser`LeafNode[_, _, KeyValuePattern[CodeParser`Source -> span_]]
+					| CodeParser`CallNode[_, _, KeyValuePattern[CodeParser`Source -> span_]],
+				___,
+				CodeParser`CallNode[
+					CodeParser`LeafNode[Symbol, "Rule", _],
+					{
+						CodeParser`LeafNode[Symbol, "TestID", _],
+						CodeParser`LeafNode[String, id_?StringQ, _]
+					},
+					_
+				],
+				___
+			},
+			_
+		] :> ToExpression[id] -> span,
+		Infinity
+	];
+
+	replacements = Map[
+		test |-> Replace[Lookup[expectedSources, test["TestID"]], {
+			span:{start_Integer, _Integer} :> Module[{indentation, actual},
+				(* Indent every line of the new expected output the same as
+				   the line that the original expected output started on. *)
+				indentation = First[
+					StringCases[
+						Last[StringSplit[StringTake[text, start - 1], "\n", All]],
+						StartOfString ~~ ws:WhitespaceCharacter... :> ws
+					],
+					""
+				];
+
+				actual = formattedTestField[test, "ActualOutput"];
+
+				StringRiffle[StringSplit[actual, "\n"], "\n" <> indentation] -> span
+			],
+			_?MissingQ :> (
+				Print[TerminalStyle[
+					"warning: unable to find source of snapshot test with TestID: " <> test["TestID"],
+					"Yellow"
+				]];
+				Nothing
+			)
+		}],
+		tests
+	];
+
+	If[replacements === {},
+		Return[Null, Module];
+	];
+
+	WithCleanup[
+		stream = OpenWrite[file],
+		WriteString[
+			stream,
+			StringReplacePart[text, Keys[replacements], Values[replacements]]
+		],
+		Close[stream]
+	];
+
+	Print[
+		TerminalStyle["Updated ", "Blue"],
+		Length[replacements],
+		Pluralize[{" snapshot", " snapshots"}, Length[replacements]],
+		" in ",
+		file
+	];
+]
+
+(*------------------------------------*)
+
+(* Returns the Wolfram Language source files in the "Kernel" extension
+   directories of the paclet in `pacletDir`. *)
+SetFallthroughError[pacletSourceFiles]
+
+pacletSourceFiles[pacletDir_?StringQ] := Module[{
+	kernelDirs = PacletTools`PacletExtensionDirectory[pacletDir, {"Kernel"}]
+},
+	RaiseAssert[
+		MatchQ[kernelDirs, <| (_ -> _?StringQ) ... |>],
+		"unexpected Kernel extension directories value: ``",
+		InputForm[kernelDirs]
+	];
+
+	Flatten @ Map[
+		dir |-> FileNames["*.wl" | "*.m", dir, Infinity],
+		Values[kernelDirs]
+	]
+]
+
+(*------------------------------------*)
+
+(*
+	Evaluate `body`, returning the time taken, the peak memory used, and the
+	number of messages issued during the evaluation.
+
+	Unlike MaxMemoryUsed[], which is the peak for the whole session,
+	MaxMemoryUsed[body] is the peak memory used while evaluating `body`.
+
+	Messages are counted using $MessageList, so messages silenced using
+	Quiet[..] or Off[..] are not counted, since the code that issued them
+	deliberately suppressed them.
+*)
+SetFallthroughError[evaluateWithFileStatistics]
+
+SetAttributes[evaluateWithFileStatistics, HoldFirst]
+
+evaluateWithFileStatistics[body_] := Module[{
+	previousMessageCount = Length[$MessageList],
+	time,
+	memory
+},
+	{time, memory} = AbsoluteTiming[MaxMemoryUsed[body]];
+
+	<|
+		"AbsoluteTimeUsed" -> time,
+		"MaxMemoryUsed" -> memory,
+		"MessageCount" -> Max[0, Length[$MessageList] - previousMessageCount]
+	|>
+]
+
+(*------------------------------------*)
+
+(* Print the statistics recorded by evaluateWithFileStatistics[..] for a test
+   file, below the results of the tests in the file. *)
+SetFallthroughError[printFileStatistics]
+
+printFileStatistics[statistics_?AssociationQ] := Print[TerminalStyle[
+	StringJoin[
+		"\t",
+		ToString @ NumberForm[statistics["AbsoluteTimeUsed"], {Infinity, 3}],
+		"s, ",
+		ToString @ NumberForm[N[statistics["MaxMemoryUsed"] / 10^6], {Infinity, 2}],
+		" MB peak memory, ",
+		ToString[statistics["MessageCount"]],
+		" ",
+		Pluralize[{"message", "messages"}, statistics["MessageCount"]]
+	],
+	"Gray"
+]]
+
+(*------------------------------------*)
+
+(*
+	Write a report of the most recent test run, containing the number of tests
+	and failures, the time taken, the peak memory used, and the number of
+	messages issued for each test file, to
+	`dir/build/wolfram-cli/test-report.json`.
+*)
+SetFallthroughError[writeTestFileReport]
+
+writeTestFileReport[dir_?StringQ, files: {___?AssociationQ}] := Module[{
+	file = FileNameJoin[{dir, "build", "wolfram-cli", "test-report.json"}]
+},
+	If[!DirectoryQ[FileNameDrop[file]],
+		CreateDirectory[FileNameDrop[file], CreateIntermediateDirectories -> True];
+	];
+
+	Export[file, <| "Files" -> files |>, "RawJSON"];
+]
+
+(*------------------------------------*)
+
+(* Print the `count` tests in `results` that took the longest time to run. *)
+SetFallthroughError[printSlowestTests]
+
+printSlowestTests[results: {___?AssociationQ}, count_?IntegerQ] := Module[{
+	slowest = Take[
+		ReverseSortBy[results, result |-> result["AbsoluteTimeUsed"]],
+		UpTo[count]
+	]
+},
+	Print[];
+	Print[TerminalStyle[
+		"Slowest " <> ToString[Length[slowest]] <> Pluralize[{" test:", " tests:"}, Length[slowest]],
+		Bold,
+		Underlined
+	]];
+	Print[];
+
+	Scan[
+		result |-> Print[
+			"\t",
+			TerminalStyle[
+				ToString @ NumberForm[result["AbsoluteTimeUsed"], {Infinity, 3}] <> "s",
+				"Yellow"
+			],
+			"\t",
+			ToString @ NumberForm[N[result["MemoryUsed"] / 10^6], {Infinity, 2}],
+			" MB",
+			"\t",
+			result["File"],
+			" ",
+			TerminalStyle[result["TestKey"], "Gray"]
+		],
+		slowest
+	];
+]
+
+(*------------------------------------*)
+
+(* Convert `quantity` to a plain number in the specified unit, or 0 if
+   `quantity` is missing. *)
+SetFallthroughError[quantityValue]
+
+quantityValue[quantity_, unit_?StringQ] := Replace[quantity, {
+	q_Quantity :> N @ QuantityMagnitude[UnitConvert[q, unit]],
+	n_?NumericQ :> N[n],
+	_ :> 0
+}]
+
+(*------------------------------------*)
+
+SetFallthroughError[printTestResult]
+
+printTestResult[
+	test_TestResultObject,
+	diffContext_?IntegerQ,
+	diffStyle: "Text" | "Event" | None
+] := Module[{},
+	Replace[test["Outcome"], {
+		"Success" :> Print[Format[test, TerminalForm]],
+		"Failure" :> Module[{
+			formattedInput,
+			a,
+			b
+		},
+			formattedInput = formattedTestField[test, "Input"];
+
+			Print[
+				Format[test, TerminalForm],
+				" -- ",
+				TerminalStyle["expected", "Red", Italic],
+				" | ",
+				TerminalStyle["actual", "Green", Italic]
+			];
+
+			Print[
+				"| ", TerminalStyle["Input:", Underlined], " ", TerminalStyle[formattedInput, "Blue"]
+			];
+
+			(* Strip the HoldForm wrapper -- this shouldn't be necessary in almost all cases. *)
+			a = Replace[test["ExpectedOutput"], HoldForm[x_] :> x];
+			b = Replace[test["ActualOutput"], HoldForm[x_] :> x];
+
+			printExprDiff[a, b, diffContext, diffStyle]
+		],
+		"Error" :> Module[{},
+			Print[Format[test, TerminalForm]];
+			Replace[test[All], {
+				KeyValuePattern[{
+					"Input" -> HoldForm[input0_],
+					"ActualOutput" -> Hold[Throw[payload_, ___]]
+				}] :> Module[{
+					payloadString = ToString[Unevaluated @ payload, InputForm],
+					formattedInput
+				},
+					formattedInput = formattedTestField[test, "Input"];
+
+					Print[
+						"| ", TerminalStyle["Input:", Underlined], " ", TerminalStyle[formattedInput, "Blue"]
+					];
+					Print[
+						TerminalStyle["Unexpected Exception: ", "Red"],
+						payloadString
+					]
+				],
+				other_ :> (
+					Print["Unhandled 'Error' test object format: ", InputForm[other]]
+				)
+			}];
+		],
+		"MessagesFailure" :> Module[{
+			formattedInput
+		},
+			formattedInput = formattedTestField[test, "Input"];
+
+			Print[
+				Format[test, TerminalForm],
+				" -- ",
+				TerminalStyle["expected", "Red", Italic],
+				" | ",
+				TerminalStyle["actual", "Green", Italic]
+			];
+
+			Print[
+				"| ", TerminalStyle["Input:", Underlined], " ", TerminalStyle[formattedInput, "Blue"]
+			];
+
+			(* Strip the HoldForm wrapper -- this shouldn't be necessary in almost all cases. *)
+			a = Replace[test["ExpectedMessages"], HoldForm[x_] :> x];
 			b = Replace[test["ActualMessages"], HoldForm[x_] :> x];
 
-			printTextualExprDiff[a, b, diffContext]
+			printExprDiff[a, b, diffContext, diffStyle]
 		],
 		(* FIXME: Expand this to cover all outcomes *)
 		other_ :> (
@@ -407,6 +1625,55 @@ printTestResult[test_TestResultObject, diffContext_?IntegerQ] := Module[{},
 
 (*------------------------------------*)
 
+SetFallthroughError[printExprDiff]
+
+printExprDiff[
+	expr1_,
+	expr2_,
+	diffContext_?IntegerQ,
+	diffStyle: "Text" | "Event" | None
+] :=
+	Replace[diffStyle, {
+		"Text" :> printTextualExprDiff[expr1, expr2, diffContext],
+		"Event" :> printClientEvent["TestDiff", <|
+			"Expected" -> formatExprForDiff[expr1],
+			"Actual" -> formatExprForDiff[expr2]
+		|>],
+		None :> Null
+	}]
+
+(*------------------------------------*)
+
+SetFallthroughError[formatExprForDiff]
+
+formatExprForDiff[expr_] := (
+	Needs["CodeFormatter`" -> None];
+
+	CodeFormatter`CodeFormat[ToString[expr, InputForm], CodeFormatter`Airiness -> 0.8]
+)
+
+(*------------------------------------*)
+
+(*
+	Print an event of type `event` with the specified data, to be handled by
+	the wolfram-cli client instead of being shown to the user.
+
+	Events are printed as a single line containing $clientEventPrefix followed
+	by the event data encoded as JSON.
+*)
+$clientEventPrefix = "wolfram-cli-event: "
+
+SetFallthroughError[printClientEvent]
+
+printClientEvent[event_?StringQ, data_?AssociationQ] :=
+	Print[$clientEventPrefix <> ExportString[
+		Prepend[data, "Event" -> event],
+		"RawJSON",
+		"Compact" -> True
+	]]
+
+(*------------------------------------*)
+
 SetFallthroughError[printTextualExprDiff]
 
 printTextualExprDiff[
@@ -420,10 +1687,8 @@ printTextualExprDiff[
 	elidedLineCount = 0,
 	limitToContext
 },
-	Needs["CodeFormatter`" -> None];
-
-	text1 = CodeFormatter`CodeFormat[ToString[expr1, InputForm], CodeFormatter`Airiness -> 0.8];
-	text2 = CodeFormatter`CodeFormat[ToString[expr2, InputForm], CodeFormatter`Airiness -> 0.8];
+	text1 = formatExprForDiff[expr1];
+	text2 = formatExprForDiff[expr2];
 
 	alignment = SequenceAlignment[
 		StringSplit[text1, "\n"],
@@ -516,44 +1781,531 @@ formattedTestField[test_TestResultObject, field_?StringQ] := Module[{
 	]
 ]
 
-SetFallthroughError[formattedTestField]
+SetFallthroughError[formattedTestField]
+
+(*====================================*)
+
+Options[CommandPacletInstall] = {
+	(*
+		Whether to install the paclet even if a newer version of it is
+		already installed.
+	*)
+	"ForceVersionInstall" -> True,
+	(*
+		Whether to only print the paclet that would be installed, without
+		building or installing it.
+	*)
+	"DryRun" -> False,
+	(*
+		Whether to print the installed paclet as JSON.
+	*)
+	"JSON" -> False
+}
+
+(*
+	`source` is either the path to a .paclet file or paclet directory, or the
+	name and version of a paclet to find in the local paclet sites.
+*)
+CommandPacletInstall[
+	source: _?StringQ | {_?StringQ, _?StringQ | All},
+	OptionsPattern[]
+] := With[{
+	forceVersion = RaiseConfirmMatch[OptionValue["ForceVersionInstall"], _?BooleanQ],
+	dryRun = RaiseConfirmMatch[OptionValue["DryRun"], _?BooleanQ],
+	json = RaiseConfirmMatch[OptionValue["JSON"], _?BooleanQ]
+},
+Module[{path, installed},
+	path = Replace[source, {
+		{name_, version_} :> localSitePacletArchive[name, version],
+		dir_?DirectoryQ /; !dryRun :> If[json,
+			(* Don't mix the build output with the JSON output. *)
+			Block[{$Output = {}}, buildPacletArchive[dir]],
+			buildPacletArchive[dir]
+		]
+	}];
+
+	If[FailureQ[path],
+		Return[path, Module]
+	];
+
+	If[dryRun,
+		installed = Append[pacletInstallSourceInfo[path], "Location" -> Null];
+
+		If[json,
+			Print[ExportString[Append[installed, "DryRun" -> True], "RawJSON"]],
+			Print[
+				"Would install paclet ",
+				StringRiffle[DeleteCases[{installed["Name"], installed["Version"]}, Null], " "],
+				" from ", path
+			]
+		];
+
+		Return[Null, Module]
+	];
+
+	installed = doPacletInstall[path, forceVersion];
+
+	If[FailureQ[installed],
+		Return[installed, Module]
+	];
+
+	installed = <|
+		"Name" -> installed["Name"],
+		"Version" -> installed["Version"],
+		"Source" -> path,
+		"Location" -> installed["Location"]
+	|>;
+
+	If[json,
+		Print[ExportString[Append[installed, "DryRun" -> False], "RawJSON"]],
+		Print[
+			TerminalStyle["Successfully installed paclet", "Green"],
+			" ", installed["Name"], " ", installed["Version"],
+			" to ", installed["Location"]
+		]
+	];
+]]
+
+(*------------------------------------*)
+
+(* Build the paclet in `pacletDir`, returning the path to the built .paclet
+   archive. *)
+SetFallthroughError[buildPacletArchive]
+
+buildPacletArchive[pacletDir_?StringQ] :=
+	Replace[pacletBuild[pacletDir, Automatic], {
+		Success["PacletBuild", KeyValuePattern[{
+			"PacletArchive" -> pacletArchive_?StringQ
+		}]] :> pacletArchive,
+		failure_?FailureQ :> (
+			Print[Format[failure, TerminalForm]];
+			failure
+		),
+		other_ :> (
+			Print["PacletBuild result had unexpected format: ", InputForm[other]];
+			Failure["UnexpectedValue", <| "Value" -> other |>]
+		)
+	}]
+
+(*------------------------------------*)
+
+(* Get the name, version, and path of the paclet directory or .paclet archive
+   at `path`, without installing it. *)
+SetFallthroughError[pacletInstallSourceInfo]
+
+pacletInstallSourceInfo[path_?StringQ] := Module[{paclet, name, version},
+	If[DirectoryQ[path],
+		paclet = PacletObject[File[path]];
+		{name, version} = {paclet["Name"], paclet["Version"]},
+		{name, version} = pacletArchiveNameAndVersion[path]
+	];
+
+	(* Use Null for an unknown name or version, so this can be exported as
+	   JSON. *)
+	<| "Name" -> name, "Version" -> version, "Source" -> path |> /. _Missing -> Null
+]
+
+(*------------------------------------*)
+
+(* Get the name and version of the paclet in a .paclet archive, using the
+   `Publisher__Name-Version.paclet` naming convention followed by PacletBuild
+   and by paclet sites. *)
+SetFallthroughError[pacletArchiveNameAndVersion]
+
+pacletArchiveNameAndVersion[file_?StringQ] :=
+	FirstCase[
+		StringCases[
+			FileBaseName[file],
+			StartOfString ~~ name__ ~~ "-" ~~ version : (DigitCharacter | ".").. ~~ EndOfString
+				:> {StringReplace[name, "__" -> "/"], version}
+		],
+		{_?StringQ, _?StringQ},
+		{Missing["Unknown"], Missing["Unknown"]}
+	]
+
+(*------------------------------------*)
+
+(* Find the .paclet archive of the newest version of the paclet `name` (or
+   the specified version) in the registered paclet sites that are local
+   directories. *)
+SetFallthroughError[localSitePacletArchive]
+
+localSitePacletArchive[name_?StringQ, version: _?StringQ | All] := Module[{
+	sites = localPacletSiteDirectories[],
+	archives
+},
+	archives = Select[
+		Flatten[FileNames["*.paclet", FileNameJoin[{#, "Paclets"}]] & /@ sites],
+		file |-> MatchQ[
+			pacletArchiveNameAndVersion[file],
+			{name, If[version === All, _?StringQ, version]}
+		]
+	];
+
+	If[archives === {},
+		Print[
+			TerminalStyle["error:", "Red", Bold],
+			" No paclet matching ",
+			If[version === All, name, name <> "@" <> version],
+			" was found in the local paclet sites",
+			If[sites === {}, ".", ":"]
+		];
+		Scan[site |-> Print["\t", site], sites];
+		Print[TerminalStyle[
+			"hint: Register a local paclet site directory with PacletSiteRegister.",
+			"Gray"
+		]];
+		Return[Failure["PacletNotFound", <| "Name" -> name, "Version" -> version |>], Module]
+	];
+
+	Last @ SortBy[
+		archives,
+		file |-> versionOrderingKey[Last[pacletArchiveNameAndVersion[file]]]
+	]
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[localPacletSiteDirectories]
+
+localPacletSiteDirectories[] :=
+	Select[
+		Cases[
+			#["URL"] & /@ PacletSites[],
+			url_?StringQ /; StringStartsQ[url, "file:", IgnoreCase -> True] :> URLDecode[
+				StringReplace[url, {
+					RegularExpression["^(?i)file:(//)?/([A-Za-z]:)"] -> "$2",
+					RegularExpression["^(?i)file:(//)?"] -> ""
+				}]
+			]
+		],
+		DirectoryQ
+	]
+
+(*------------------------------------*)
+
+SetFallthroughError[versionOrderingKey]
+
+versionOrderingKey[version_?StringQ] :=
+	PadRight[FromDigits /@ StringSplit[version, "."], 5]
+
+(*------------------------------------*)
+
+(* Used by `$ wolfram paclet install` and `$ wolfram paclet build --install` *)
+SetFallthroughError[doPacletInstall]
+
+doPacletInstall[
+	pacletFile_?StringQ,
+	forceVersion: _?BooleanQ : True
+] := Module[{
+	result = PacletInstall[pacletFile, ForceVersionInstall -> forceVersion]
+},
+	Replace[result, {
+		paclet : HoldPattern @ PacletObject[_] :> paclet,
+		failure_?FailureQ :> (
+			Print["Error installing paclet: ", Format[failure, TerminalForm]];
+			failure
+		),
+		other_ :> (
+			Print["PacletInstall result had unexpected format: ", InputForm[other]];
+			Return[Failure["UnexpectedValue"], Module]
+		)
+	}]
+]
+
+(*====================================*)
+
+Options[CommandPacletList] = {
+	(*
+		Whether to print the list of paclets as JSON instead of as a table.
+	*)
+	"JSON" -> False
+}
+
+CommandPacletList[
+	pattern: _?StringQ | All,
+	OptionsPattern[]
+] := With[{
+	json = RaiseConfirmMatch[OptionValue["JSON"], _?BooleanQ]
+},
+Module[{paclets, entries},
+	paclets = installedPaclets[];
+
+	If[pattern =!= All,
+		paclets = Select[
+			paclets,
+			paclet |-> StringMatchQ[paclet["Name"], pattern, IgnoreCase -> True]
+		];
+	];
+
+	entries = installedPacletEntry /@ paclets;
+
+	Which[
+		json,
+			Print[ExportString[entries, "RawJSON"]],
+		entries === {},
+			Print[TerminalStyle["info: No installed paclets were found.", "Gray"]],
+		True,
+			printInstalledPaclets[entries]
+	];
+]]
+
+(*------------------------------------*)
+
+(* Get every paclet known to the paclet manager, including disabled paclets,
+   sorted by name. *)
+SetFallthroughError[installedPaclets]
+
+installedPaclets[] :=
+	SortBy[
+		DeleteDuplicatesBy[
+			Join[
+				PacletFind[All],
+				PacletFind[All, <| "Enabled" -> False |>]
+			],
+			paclet |-> ExpandFileName[paclet["Location"]]
+		],
+		paclet |-> paclet["Name"]
+	]
+
+(*------------------------------------*)
+
+SetFallthroughError[installedPacletEntry]
+
+installedPacletEntry[paclet_PacletObject] := Module[{
+	location = ExpandFileName[paclet["Location"]],
+	(* PacletFind returns the enabled paclets with this name in the order of
+	   precedence used to resolve the name. *)
+	enabled = ExpandFileName[#["Location"]] & /@ PacletFind[paclet["Name"]]
+},
+	<|
+		"Name" -> paclet["Name"],
+		"Version" -> paclet["Version"],
+		"Location" -> location,
+		"Enabled" -> MemberQ[enabled, location],
+		"Loaded" -> AnyTrue[paclet["Context"], context |-> MemberQ[$Packages, context]],
+		"Shadowed" -> MemberQ[Rest[enabled], location]
+	|>
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[printInstalledPaclets]
+
+printInstalledPaclets[entries: {__?AssociationQ}] := Module[{
+	nameWidth = Max[StringLength[Lookup[entries, "Name"]]],
+	versionWidth = Max[StringLength[Lookup[entries, "Version"]]],
+	statuses,
+	statusWidth
+},
+	statuses = Map[
+		entry |-> StringRiffle[
+			Pick[
+				{"enabled", "disabled", "loaded", "shadowed"},
+				{entry["Enabled"], !entry["Enabled"], entry["Loaded"], entry["Shadowed"]}
+			],
+			", "
+		],
+		entries
+	];
+
+	statusWidth = Max[StringLength[statuses]];
+
+	MapThread[
+		{entry, status} |-> (
+			Print[
+				StringPadRight[entry["Name"], nameWidth],
+				"  ",
+				StringPadRight[entry["Version"], versionWidth],
+				"  ",
+				TerminalStyle[
+					StringPadRight[status, statusWidth],
+					Which[
+						entry["Shadowed"] || !entry["Enabled"], "Yellow",
+						entry["Loaded"], "Green",
+						True, "Gray"
+					]
+				],
+				"  ",
+				entry["Location"]
+			]
+		),
+		{entries, statuses}
+	];
+]
+
+(*====================================*)
+
+Options[CommandPacletUninstall] = {
+	(*
+		Whether to uninstall the matching paclets. If False, the paclets that
+		would be uninstalled are printed, and nothing is uninstalled.
+	*)
+	"Confirmed" -> False
+}
+
+CommandPacletUninstall[
+	name: _?StringQ,
+	version: _?StringQ | All,
+	OptionsPattern[]
+] := With[{
+	confirmed = RaiseConfirmMatch[OptionValue["Confirmed"], _?BooleanQ]
+},
+Module[{paclets, failures},
+	paclets = Select[
+		installedPaclets[],
+		paclet |-> paclet["Name"] === name && (version === All || paclet["Version"] === version)
+	];
+
+	If[paclets === {},
+		Print[
+			TerminalStyle["error:", "Red", Bold],
+			" No installed paclet matches ",
+			If[version === All, name, name <> "@" <> version]
+		];
+		Return[<| "PacletCount" -> 0, "FailureCount" -> 0 |>, Module];
+	];
+
+	If[!confirmed,
+		Print[
+			"The following ",
+			Pluralize[{"paclet", "paclets"}, Length[paclets]],
+			" will be uninstalled:"
+		];
+		Print[];
+		Scan[
+			paclet |-> Print["\t", paclet["Name"], " ", paclet["Version"], "\t", paclet["Location"]],
+			paclets
+		];
+		Print[];
+		Return[<| "PacletCount" -> Length[paclets], "FailureCount" -> 0 |>, Module];
+	];
+
+	failures = Select[
+		paclets,
+		paclet |-> Replace[PacletUninstall[paclet], {
+			failure_?FailureQ :> (
+				Print[
+					TerminalStyle["error:", "Red", Bold],
+					" Error uninstalling paclet ",
+					paclet["Name"], " ", paclet["Version"], ": ",
+					Format[failure, TerminalForm]
+				];
+				True
+			),
+			_ :> (
+				Print[
+					TerminalStyle["Uninstalled", "Green"],
+					" paclet ", paclet["Name"], " ", paclet["Version"]
+				];
+				False
+			)
+		}]
+	];
+
+	<| "PacletCount" -> Length[paclets], "FailureCount" -> Length[failures] |>
+]]
 
 (*====================================*)
 
-CommandPacletInstall[pacletFile_?StringQ] :=
-	doPacletInstall[pacletFile]
-
-(*------------------------------------*)
+Options[CommandPacletBuild] = {
+	(*
+		Whether to build the paclet in a subkernel launched with an empty,
+		temporary user base directory and without running init.m files.
+	*)
+	"Isolated" -> False,
+	(*
+		Whether to print a single timestamped status line describing the
+		outcome of the build, instead of the full build output.
+	*)
+	"Compact" -> False
+}
 
-(* Used by `$ wolfram paclet install` and `$ wolfram paclet build --install` *)
-doPacletInstall[pacletFile_?StringQ] := Module[{
-	(* TODO: Expose this ForceVersionInstall value via the command-line
-	         interface? *)
-	result = PacletInstall[pacletFile, ForceVersionInstall -> True]
+CommandPacletBuild[
+	pacletDir: _?StringQ,
+	buildDir: _?StringQ | Automatic,
+	install: _?BooleanQ,
+	OptionsPattern[]
+] := With[{
+	isolated = RaiseConfirmMatch[OptionValue["Isolated"], _?BooleanQ],
+	compact = RaiseConfirmMatch[OptionValue["Compact"], _?BooleanQ]
 },
+Module[{result, installed},
+
+	result = If[isolated,
+		isolatedPacletBuild[pacletDir, buildDir],
+		pacletBuild[pacletDir, buildDir]
+	];
+
 	Replace[result, {
-		HoldPattern @ PacletObject[_] :> (
-			Print[TerminalStyle["Successfully installed paclet.", "Green"]];
+		Success["PacletBuild", KeyValuePattern[{
+			"PacletArchive" -> pacletArchive_?StringQ,
+			"TotalTime" -> time:Quantity[_, "Seconds"]
+		}]] :> (
+			If[!compact,
+				Print[TerminalStyle["Build succeeded.", "Green"], " ", "Took ", ToString[time]];
+				Print["Paclet Archive: ", InputForm[pacletArchive]];
+			];
+
+			If[install,
+				installed = doPacletInstall[pacletArchive];
+
+				If[FailureQ[installed],
+					Return[installed, Module]
+				];
+			];
+
+			Which[
+				compact,
+					Print[
+						buildStatusTimestamp[],
+						TerminalStyle["Build succeeded", "Green"],
+						If[install, " and installed", ""],
+						" in ", ToString[time], ": ",
+						FileNameTake[pacletArchive]
+					],
+				install,
+					Print[TerminalStyle["Successfully installed paclet.", "Green"]]
+			];
+
+			<| "PacletArchive" -> pacletArchive |>
 		),
-		failure_?FailureQ :> (
-			Print["Error installing paclet: ", Format[failure, TerminalForm]];
+		failure:Failure[tag_?StringQ, _] :> (
+			If[compact,
+				Print[
+					buildStatusTimestamp[],
+					TerminalStyle["Build failed", "Red"],
+					": ",
+					Replace[failure["Message"], Except[_?StringQ] :> tag]
+				],
+				Print[Format[failure, TerminalForm]]
+			];
+
 			failure
 		),
 		other_ :> (
-			Print["PacletInstall result had unexpected format: ", InputForm[other]];
+			Print["PacletBuild result had unexpected format: ", InputForm[other]];
+			(* TODO: Standardize on Failure's and how they map to exit codes. *)
 			Return[Failure["UnexpectedValue"], Module]
 		)
 	}]
-]
+]]
 
-(*====================================*)
+(*------------------------------------*)
 
-CommandPacletBuild[
-	pacletDir: _?StringQ,
-	buildDir: _?StringQ | Automatic,
-	install: _?BooleanQ
-] := Module[{result},
+SetFallthroughError[buildStatusTimestamp]
+
+buildStatusTimestamp[] :=
+	TerminalStyle["[" <> DateString["Time"] <> "] ", "Gray"]
+
+(*------------------------------------*)
 
+SetFallthroughError[pacletBuild]
+
+pacletBuild[
+	pacletDir: _?StringQ,
+	buildDir: _?StringQ | Automatic
+] := (
 	(* FIXME: Workaround bug: The WolframKernel will crash when loading the
 		CodeParser dynamic library if that happens after a call to FileHash[..]
 		in PacletBuild (the exact underlying cause is unclear), which manifests
@@ -563,29 +2315,43 @@ CommandPacletBuild[
 
 	Needs["PacletTools`" -> None];
 
-	result = PacletTools`PacletBuild[pacletDir, buildDir];
+	PacletTools`PacletBuild[pacletDir, buildDir]
+)
 
-	Replace[result, {
-		Success["PacletBuild", KeyValuePattern[{
-			"PacletArchive" -> pacletArchive_?StringQ,
-			"TotalTime" -> time:Quantity[_, "Seconds"]
-		}]] :> (
-			Print[TerminalStyle["Build succeeded.", "Green"], " ", "Took ", ToString[time]];
-			Print["Paclet Archive: ", InputForm[pacletArchive]];
+(*------------------------------------*)
 
-			If[install,
-				doPacletInstall[pacletArchive]
-			]
-		),
-		failure:Failure[tag_?StringQ, _] :> (
-			Print[Format[failure, TerminalForm]];
-		),
-		other_ :> (
-			Print["PacletBuild result had unexpected format: ", InputForm[other]];
-			(* TODO: Standardize on Failure's and how they map to exit codes. *)
-			Return[Failure["UnexpectedValue"], Module]
-		)
-	}]
+(* Build a paclet in an isolated subkernel, in which only the dependencies
+   declared by the paclet are installed. *)
+SetFallthroughError[isolatedPacletBuild]
+
+isolatedPacletBuild[
+	pacletDir: _?StringQ,
+	buildDir: _?StringQ | Automatic
+] := Module[{linkObj, result},
+	linkObj = launchSubkernel[True];
+
+	LinkRead[linkObj]; (* Read the InputNamePacket. *)
+
+	LinkWrite[linkObj, Unevaluated @ EvaluatePacket @ (
+		SetOptions[$Output, PageWidth -> Infinity];
+
+		PacletInstall /@ PacletObject[File[pacletDir]]["Dependencies"];
+
+		(* FIXME: Workaround the same CodeParser crash as in
+			CommandPacletBuild. *)
+		Needs["CodeParser`" -> None];
+		CodeParser`CodeConcreteParse["2+2"];
+
+		Needs["PacletTools`" -> None];
+
+		PacletTools`PacletBuild[pacletDir, buildDir]
+	)];
+
+	result = forwardSubkernelOutput[linkObj];
+
+	closeSubkernel[linkObj];
+
+	result
 ]
 
 (*====================================*)
@@ -634,8 +2400,432 @@ CommandPacletDoc[
 		),
 		other_ :> (
 			Print[Format[Failure["UnexpectedValue", other], TerminalForm]];
+			$Failed
 		)
+	}]
+]
+
+(*====================================*)
+
+Options[CommandPacletBench] = {
+	(*
+		Number of times to evaluate each benchmark before it is measured.
+	*)
+	"Warmup" -> 3,
+	(*
+		Number of times each benchmark is evaluated and measured.
+	*)
+	"Repetitions" -> 10,
+	(*
+		Name of the saved baseline to compare the benchmark results against,
+		or Automatic to compare against the previous benchmark run.
+	*)
+	"Baseline" -> Automatic,
+	(*
+		Name under which to save the benchmark results as a baseline, or None.
+	*)
+	"SaveBaseline" -> None,
+	(*
+		Percentage by which the median time of a benchmark must exceed the
+		baseline for the benchmark to be reported as a regression.
+	*)
+	"Threshold" -> 10
+}
+
+(*
+	Handle `$ wolfram paclet bench`
+
+	Each benchmark file is a .wl file containing Benchmark[name, expr]
+	expressions. Each benchmark file is run in a fresh subkernel, in which any
+	other top-level expressions in the file are evaluated normally, in order.
+*)
+CommandPacletBench[
+	pacletDir: _?StringQ,
+	benchPath: _?StringQ | Automatic : Automatic,
+	OptionsPattern[]
+] := With[{
+	warmup = RaiseConfirmMatch[OptionValue["Warmup"], _?IntegerQ],
+	repetitions = RaiseConfirmMatch[OptionValue["Repetitions"], _?IntegerQ],
+	baseline = RaiseConfirmMatch[OptionValue["Baseline"], _?StringQ | Automatic],
+	saveBaseline = RaiseConfirmMatch[OptionValue["SaveBaseline"], _?StringQ | None],
+	threshold = RaiseConfirmMatch[OptionValue["Threshold"], _?NumericQ]
+},
+Module[{
+	benchFiles,
+	baselineResults,
+	linkObj,
+	fileResults,
+	results = {},
+	failedFiles = 0,
+	statistics,
+	regressions
+},
+	RaiseAssert[repetitions > 0, "number of repetitions must be positive"];
+
+	benchFiles = benchmarkFiles[pacletDir, benchPath];
+
+	If[benchFiles === {},
+		Print[TerminalStyle["info: No benchmark files were found.", "Gray"]];
+		Print[TerminalStyle[
+			"hint: Benchmarks are .wl files containing Benchmark[name, expr] expressions, in the \"Benchmarks\" directory of the paclet.",
+			"Gray"
+		]];
+		Return[
+			<| "BenchmarkCount" -> 0, "RegressionCount" -> 0, "FailedFileCount" -> 0 |>,
+			Module
+		];
+	];
+
+	(* Read the baseline before the results of this run replace the results
+	   of the previous run. *)
+	baselineResults = Replace[baseline, {
+		Automatic :> readBenchmarkResults[pacletDir, "latest"],
+		name_?StringQ :> Replace[readBenchmarkResults[pacletDir, name], {
+			None :> Raise[
+				WolframCLIError,
+				"no benchmark baseline named `` was found at: ``",
+				InputForm[name],
+				benchmarkResultsFile[pacletDir, name]
+			],
+			other_ :> other
+		}]
+	}];
+
+	(*----------------------------------------------*)
+	(* Run each benchmark file in a fresh subkernel *)
+	(*----------------------------------------------*)
+
+	Scan[
+		file |-> (
+			Print[TerminalStyle["FILE:", Bold, Underlined], " ", file];
+
+			linkObj = launchSubkernel[False];
+
+			LinkRead[linkObj]; (* Read the InputNamePacket. *)
+
+			LinkWrite[linkObj, Unevaluated @ EvaluatePacket @ (
+				SetOptions[$Output, PageWidth -> Infinity];
+
+				PacletDirectoryLoad[pacletDir];
+
+				Needs["ConnorGray`WolframCLI`" -> None];
+
+				runBenchmarkFile[file, warmup, repetitions]
+			)];
+
+			fileResults = forwardSubkernelOutput[linkObj];
+
+			closeSubkernel[linkObj];
+
+			If[MatchQ[fileResults, {___?AssociationQ}],
+				results = Join[results, fileResults];
+				,
+				failedFiles += 1;
+				Print[TerminalStyle[
+					"warning: Benchmark file did not run successfully: " <> file,
+					"Yellow"
+				]];
+			];
+		),
+		benchFiles
+	];
+
+	statistics = Association @ Map[
+		result |-> result["Name"] -> <|
+			"File" -> result["File"],
+			"Median" -> N @ Median[result["Times"]],
+			"Min" -> Min[result["Times"]],
+			"Max" -> Max[result["Times"]],
+			"Memory" -> N @ Median[result["Memory"]]
+		|>,
+		results
+	];
+
+	(*-----------------------------------------*)
+	(* Report and record the benchmark results *)
+	(*-----------------------------------------*)
+
+	regressions = printBenchmarkReport[statistics, baselineResults, threshold];
+
+	writeBenchmarkResults[pacletDir, "latest", statistics];
+
+	If[StringQ[saveBaseline],
+		Print[];
+		Print[
+			"Saved benchmark baseline ",
+			InputForm[saveBaseline],
+			" to: ",
+			writeBenchmarkResults[pacletDir, saveBaseline, statistics]
+		];
+	];
+
+	<|
+		"BenchmarkCount" -> Length[statistics],
+		"RegressionCount" -> regressions,
+		"FailedFileCount" -> failedFiles
+	|>
+]]
+
+(*------------------------------------*)
+
+(* Returns the benchmark files in `benchPath`, or in the "Benchmarks"
+   extension directory or Benchmarks/ directory of the paclet. *)
+SetFallthroughError[benchmarkFiles]
+
+benchmarkFiles[pacletDir_?StringQ, benchPath: _?StringQ | Automatic] := Module[{
+	benchDirs
+},
+	Needs["PacletTools`" -> None];
+
+	benchDirs = Replace[benchPath, {
+		Automatic :> Replace[
+			Values @ PacletTools`PacletExtensionDirectory[pacletDir, {"Benchmarks"}],
+			{
+				dirs:{__?DirectoryQ} :> dirs,
+				_ :> Select[{FileNameJoin[{pacletDir, "Benchmarks"}]}, DirectoryQ]
+			}
+		],
+		dir_?DirectoryQ :> {dir},
+		file_ /; FileType[file] === File :> Return[{ExpandFileName[file]}, Module],
+		other_ :> Raise[
+			WolframCLIError,
+			"benchmark path is not a file or directory: ``",
+			InputForm[other]
+		]
 	}];
+
+	Map[ExpandFileName, Flatten @ Map[FileNames["*.wl", #] &, benchDirs]]
+]
+
+(*------------------------------------*)
+
+(*
+	Evaluate the top-level expressions in the benchmark file `file`, measuring
+	each Benchmark[name, expr] expression, and return the measurements.
+
+	This is evaluated in the subkernel launched for `file`.
+*)
+SetFallthroughError[runBenchmarkFile]
+
+runBenchmarkFile[
+	file_?StringQ,
+	warmup_?IntegerQ,
+	repetitions_?IntegerQ
+] := Module[{
+	heldExprs,
+	results = {}
+},
+	heldExprs = RaiseConfirmMatch[
+		Import[file, "HeldExpressions"],
+		{___HoldComplete}
+	];
+
+	Block[{$InputFileName = file},
+		Scan[
+			Replace[{
+				HoldComplete[head_Symbol[name_?StringQ, expr_]] /; (
+					SymbolName[Unevaluated[head]] === "Benchmark"
+				) :> Module[{measurements},
+					Do[expr, warmup];
+
+					(* Unlike MaxMemoryUsed[], which is the peak for the
+					   whole session, MaxMemoryUsed[expr] is the peak memory
+					   used while evaluating this benchmark. *)
+					measurements = Table[
+						ClearSystemCache[];
+						AbsoluteTiming[MaxMemoryUsed[expr]],
+						repetitions
+					];
+
+					Print[
+						"\t",
+						name,
+						" ",
+						TerminalStyle[
+							formatSeconds[Median[measurements[[All, 1]]]],
+							"Gray"
+						]
+					];
+
+					AppendTo[results, <|
+						"Name" -> name,
+						"File" -> file,
+						"Times" -> measurements[[All, 1]],
+						"Memory" -> measurements[[All, 2]]
+					|>];
+				],
+				held_HoldComplete :> ReleaseHold[held]
+			}],
+			heldExprs
+		];
+	];
+
+	results
+]
+
+(*------------------------------------*)
+
+(*
+	Print the median, minimum, and maximum time and the median memory used by
+	each benchmark, along with the change in median time from `baseline`, and
+	return the number of benchmarks whose median time regressed by more than
+	`threshold` percent.
+*)
+SetFallthroughError[printBenchmarkReport]
+
+printBenchmarkReport[
+	statistics_?AssociationQ,
+	baseline: _?AssociationQ | None,
+	threshold_?NumericQ
+] := Module[{
+	regressions = 0
+},
+	Print[];
+	Print[TerminalStyle["Benchmarks:", Bold, Underlined]];
+	Print[];
+
+	KeyValueMap[
+		Function[{name, stats},
+			Print[
+				"\t",
+				TerminalStyle[formatSeconds[stats["Median"]], "Yellow"],
+				"\t",
+				TerminalStyle[
+					"[" <> formatSeconds[stats["Min"]] <> " .. " <> formatSeconds[stats["Max"]] <> "]",
+					"Gray"
+				],
+				"\t",
+				ToString @ NumberForm[stats["Memory"] / 10^6, {Infinity, 2}],
+				" MB",
+				"\t",
+				name,
+				" ",
+				Replace[Lookup[Replace[baseline, None -> <||>], name, None], {
+					KeyValuePattern["Median" -> previous_?Positive] :> With[{
+						change = 100 * (stats["Median"] - previous) / previous
+					},
+						Which[
+							change > threshold,
+								regressions += 1;
+								TerminalStyle["REGRESSION " <> formatPercentChange[change], "Red"],
+							change < -threshold,
+								TerminalStyle[formatPercentChange[change], "Green"],
+							True,
+								TerminalStyle[formatPercentChange[change], "Gray"]
+						]
+					],
+					_ :> TerminalStyle["(new)", "Gray"]
+				}]
+			]
+		],
+		statistics
+	];
+
+	Print[];
+
+	If[baseline === None,
+		Print[TerminalStyle[
+			"info: No previous benchmark results were found to compare against.",
+			"Gray"
+		]];
+		,
+		If[regressions > 0,
+			Print[TerminalStyle[
+				ToString[regressions] <> " " <> Pluralize[{"benchmark", "benchmarks"}, regressions]
+					<> " regressed by more than " <> ToString[threshold] <> "%",
+				"Red"
+			]],
+			Print[TerminalStyle[
+				"No benchmarks regressed by more than " <> ToString[threshold] <> "%",
+				"Green"
+			]]
+		];
+	];
+
+	regressions
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[formatSeconds]
+
+formatSeconds[seconds_?NumericQ] := If[seconds < 1,
+	ToString @ NumberForm[N[seconds * 1000], {Infinity, 3}] <> " ms",
+	ToString @ NumberForm[N[seconds], {Infinity, 3}] <> " s"
+]
+
+(*------------------------------------*)
+
+SetFallthroughError[formatPercentChange]
+
+formatPercentChange[change_?NumericQ] :=
+	If[change >= 0, "+", ""] <> ToString @ NumberForm[N[change], {Infinity, 1}] <> "%"
+
+(*------------------------------------*)
+
+(*
+	Benchmark results are recorded in the form:
+
+		<| name -> <|
+			"File" -> file,
+			"Median" -> seconds,
+			"Min" -> seconds,
+			"Max" -> seconds,
+			"Memory" -> bytes
+		|> |>
+
+	The results of the most recent run are recorded as "latest", and named
+	baselines are recorded alongside them.
+*)
+SetFallthroughError[benchmarkResultsFile]
+
+benchmarkResultsFile[pacletDir_?StringQ, name_?StringQ] :=
+	FileNameJoin[{pacletDir, "build", "wolfram-cli", "benchmarks", name <> ".json"}]
+
+(*------------------------------------*)
+
+(* Returns the benchmark results recorded under `name`, or None if there
+   are none. *)
+SetFallthroughError[readBenchmarkResults]
+
+readBenchmarkResults[pacletDir_?StringQ, name_?StringQ] := Module[{
+	file = benchmarkResultsFile[pacletDir, name],
+	results
+},
+	If[!FileExistsQ[file],
+		Return[None, Module];
+	];
+
+	results = Import[file, "RawJSON"];
+
+	If[!MatchQ[results, <| (_?StringQ -> KeyValuePattern["Median" -> _?NumericQ]) ... |>],
+		Print[TerminalStyle[
+			"warning: Ignoring malformed benchmark results file: " <> file,
+			"Yellow"
+		]];
+		Return[None, Module];
+	];
+
+	results
+]
+
+(*------------------------------------*)
+
+(* Record `statistics` under `name`, returning the file written. *)
+SetFallthroughError[writeBenchmarkResults]
+
+writeBenchmarkResults[
+	pacletDir_?StringQ,
+	name_?StringQ,
+	statistics_?AssociationQ
+] := Module[{
+	file = benchmarkResultsFile[pacletDir, name]
+},
+	If[!DirectoryQ[FileNameDrop[file]],
+		CreateDirectory[FileNameDrop[file], CreateIntermediateDirectories -> True];
+	];
+
+	Export[file, statistics, "RawJSON"]
 ]
 
 (*====================================*)
@@ -0,0 +1,257 @@
+//! Cache used by `$ wolfram-cli paclet build` to skip rebuilding paclets whose
+//! inputs haven't changed since they were last built.
+//!
+//! The inputs of a build are the files in the paclet directory (including
+//! `PacletInfo.wl` and any `LibraryResources`) and the build options. A hash
+//! of the inputs is recorded along with the built `.paclet` archive after
+//! each successful build.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub struct BuildCache {
+	/// File the last successful build is recorded in.
+	file: PathBuf,
+	inputs_hash: String,
+}
+
+/// Contents of the build cache file.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CachedBuild {
+	inputs_hash: String,
+	paclet_archive: PathBuf,
+}
+
+impl BuildCache {
+	/// Hash the inputs of a build of the paclet in `paclet_dir`, ignoring
+	/// the files in the `ignored` directories.
+	///
+	/// `options` are the build options that affect the contents of the built
+	/// paclet archive.
+	pub fn new(
+		paclet_dir: &Path,
+		build_dir: &Path,
+		ignored: &[PathBuf],
+		options: &[(&str, String)],
+	) -> Result<BuildCache, String> {
+		let mut files = Vec::new();
+		collect_input_files(paclet_dir, ignored, &mut files)?;
+		files.sort();
+
+		let mut hasher = Sha256::new();
+
+		// Cached builds can't be reused by a different version of wolfram-cli,
+		// which may build paclets differently.
+		hasher.update(env!("CARGO_PKG_VERSION"));
+
+		for (name, value) in options {
+			hasher.update(format!("\n{name}={value}"));
+		}
+
+		for file in files {
+			let relative = file
+				.strip_prefix(paclet_dir)
+				.expect("build input file is not in the paclet directory");
+
+			let contents = std::fs::read(&file).map_err(|err| {
+				format!("error reading {}: {err}", file.display())
+			})?;
+
+			hasher.update(b"\n");
+			hasher.update(relative.to_string_lossy().as_bytes());
+			hasher.update(contents.len().to_le_bytes());
+			hasher.update(&contents);
+		}
+
+		Ok(BuildCache {
+			file: build_dir.join("wolfram-cli").join("build-cache.json"),
+			inputs_hash: format!("{:x}", hasher.finalize()),
+		})
+	}
+
+	/// Get the `.paclet` archive built by the last successful build, if the
+	/// inputs of that build were the same as the current inputs and the
+	/// archive still exists.
+	pub fn cached_archive(&self) -> Option<PathBuf> {
+		let contents = std::fs::read_to_string(&self.file).ok()?;
+
+		let cached: CachedBuild = serde_json::from_str(&contents).ok()?;
+
+		if cached.inputs_hash != self.inputs_hash
+			|| !cached.paclet_archive.is_file()
+		{
+			return None;
+		}
+
+		Some(cached.paclet_archive)
+	}
+
+	/// Record that the current inputs were successfully built into
+	/// `paclet_archive`.
+	pub fn save(&self, paclet_archive: &Path) -> Result<(), String> {
+		let cached = CachedBuild {
+			inputs_hash: self.inputs_hash.clone(),
+			paclet_archive: paclet_archive.to_path_buf(),
+		};
+
+		let contents = serde_json::to_string_pretty(&cached)
+			.expect("error serializing build cache");
+
+		if let Some(parent) = self.file.parent() {
+			std::fs::create_dir_all(parent).map_err(|err| {
+				format!("error creating directory {}: {err}", parent.display())
+			})?;
+		}
+
+		std::fs::write(&self.file, contents).map_err(|err| {
+			format!("error writing {}: {err}", self.file.display())
+		})
+	}
+}
+
+fn collect_input_files(
+	dir: &Path,
+	ignored: &[PathBuf],
+	files: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+	let read_error =
+		|path: &Path, err| format!("error reading {}: {err}", path.display());
+
+	let entries = std::fs::read_dir(dir).map_err(|err| read_error(dir, err))?;
+
+	for entry in entries {
+		let path = entry.map_err(|err| read_error(dir, err))?.path();
+
+		if ignored.iter().any(|ignored| path.starts_with(ignored)) {
+			continue;
+		}
+
+		if path.is_dir() {
+			collect_input_files(&path, ignored, files)?;
+		} else {
+			files.push(path);
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::test_util::TempDir;
+
+	fn cache(paclet_dir: &Path, options: &[(&str, String)]) -> BuildCache {
+		let build_dir = paclet_dir.join("build");
+
+		BuildCache::new(
+			paclet_dir,
+			&build_dir,
+			&[build_dir.clone(), paclet_dir.join(".git")],
+			options,
+		)
+		.unwrap()
+	}
+
+	fn inputs_hash(paclet_dir: &Path) -> String {
+		cache(paclet_dir, &[]).inputs_hash
+	}
+
+	#[test]
+	fn test_inputs_hash() {
+		let dir = TempDir::new("build-cache-inputs-hash");
+		dir.write("PacletInfo.wl", "PacletObject[<|\"Name\" -> \"A\"|>]");
+		dir.write("Kernel/A.wl", "f[] := 1");
+
+		let original = inputs_hash(dir.path());
+
+		assert_eq!(inputs_hash(dir.path()), original);
+
+		// Files in ignored directories are not inputs.
+		dir.write("build/A.paclet", "archive");
+		dir.write(".git/HEAD", "ref");
+		assert_eq!(inputs_hash(dir.path()), original);
+
+		// Changing the contents of a file changes the hash.
+		dir.write("Kernel/A.wl", "f[] := 2");
+		let changed = inputs_hash(dir.path());
+		assert_ne!(changed, original);
+
+		// Renaming a file changes the hash.
+		std::fs::rename(
+			dir.path().join("Kernel/A.wl"),
+			dir.path().join("Kernel/B.wl"),
+		)
+		.unwrap();
+		assert_ne!(inputs_hash(dir.path()), changed);
+
+		// Adding a file changes the hash, even if it's empty.
+		let renamed = inputs_hash(dir.path());
+		dir.write("Kernel/Empty.wl", "");
+		assert_ne!(inputs_hash(dir.path()), renamed);
+	}
+
+	#[test]
+	fn test_inputs_hash_options() {
+		let dir = TempDir::new("build-cache-options");
+		dir.write("PacletInfo.wl", "PacletObject[<|\"Name\" -> \"A\"|>]");
+
+		let hash = |isolated: bool| {
+			cache(dir.path(), &[("Isolated", isolated.to_string())]).inputs_hash
+		};
+
+		assert_eq!(hash(false), hash(false));
+		assert_ne!(hash(false), hash(true));
+		assert_ne!(hash(false), inputs_hash(dir.path()));
+	}
+
+	#[test]
+	fn test_cached_archive() {
+		let dir = TempDir::new("build-cache-archive");
+		dir.write("PacletInfo.wl", "PacletObject[<|\"Name\" -> \"A\"|>]");
+
+		// Nothing has been built yet.
+		assert_eq!(cache(dir.path(), &[]).cached_archive(), None);
+
+		let archive = dir.write("build/A-0.0.1.paclet", "archive");
+
+		cache(dir.path(), &[]).save(&archive).unwrap();
+
+		assert_eq!(
+			cache(dir.path(), &[]).cached_archive(),
+			Some(archive.clone())
+		);
+
+		// The cache is not used with different build options.
+		assert_eq!(
+			cache(dir.path(), &[("Isolated", "true".to_owned())])
+				.cached_archive(),
+			None
+		);
+
+		// The cache is not used if the archive was deleted.
+		std::fs::remove_file(&archive).unwrap();
+		assert_eq!(cache(dir.path(), &[]).cached_archive(), None);
+
+		// The cache is used again once the archive is restored.
+		dir.write("build/A-0.0.1.paclet", "archive");
+		assert_eq!(cache(dir.path(), &[]).cached_archive(), Some(archive));
+
+		// The cache is not used after the paclet changes.
+		dir.write("Kernel/A.wl", "f[] := 1");
+		assert_eq!(cache(dir.path(), &[]).cached_archive(), None);
+	}
+
+	#[test]
+	fn test_malformed_cache_file() {
+		let dir = TempDir::new("build-cache-malformed");
+		dir.write("PacletInfo.wl", "PacletObject[<|\"Name\" -> \"A\"|>]");
+		dir.write("build/wolfram-cli/build-cache.json", "not json");
+
+		assert_eq!(cache(dir.path(), &[]).cached_archive(), None);
+	}
+}
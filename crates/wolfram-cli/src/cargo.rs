@@ -0,0 +1,354 @@
+//! Building the Rust LibraryLink crates of paclets.
+//!
+//! A paclet with a `LibraryResources` extension can declare a Cargo crate
+//! that is built into a dynamic library used by the paclet:
+//!
+//! ```wolfram
+//! {"LibraryResources", "CargoManifest" -> "../crates/my-link/Cargo.toml"}
+//! ```
+//!
+//! The `"CargoManifest"` path is relative to the paclet directory. If it isn't
+//! specified, the `Cargo.toml` file in the paclet directory is used, if there
+//! is one.
+//!
+//! The dynamic library built by the crate is copied into the
+//! `LibraryResources/<$SystemID>/` directory of the paclet, where it can be
+//! loaded using `LibraryFunctionLoad["lib<name>", ..]`.
+
+use std::{
+	io::BufRead,
+	path::{Path, PathBuf},
+	process::{Command, Stdio},
+};
+
+use colored::Colorize;
+
+use crate::paclet_info::{PacletInfo, Value};
+
+/// A Cargo crate declared by a paclet.
+pub struct LibraryCrate {
+	/// Path of the `Cargo.toml` file of the crate.
+	pub manifest: PathBuf,
+	/// `LibraryResources` directory of the paclet.
+	resources_dir: PathBuf,
+}
+
+impl LibraryCrate {
+	/// Get the Cargo crate declared by the paclet in `paclet_dir`, if any.
+	pub fn find(
+		paclet_dir: &Path,
+		info: &PacletInfo,
+	) -> Result<Option<LibraryCrate>, String> {
+		let Some(extension) = info.extensions.iter().find(|extension| {
+			extension.kind == "LibraryResources"
+				|| extension.kind == "LibraryLink"
+		}) else {
+			return Ok(None);
+		};
+
+		let resources_dir = paclet_dir
+			.join(extension.root.as_deref().unwrap_or("LibraryResources"));
+
+		let declared = extension
+			.options
+			.iter()
+			.find(|(key, _)| key == "CargoManifest");
+
+		let manifest = match declared {
+			Some((_, Value::String(manifest))) => {
+				let manifest = paclet_dir.join(manifest);
+
+				if !manifest.is_file() {
+					return Err(format!(
+						"\"CargoManifest\" file of paclet {} does not exist: {}",
+						info.name,
+						manifest.display()
+					));
+				}
+
+				manifest
+			},
+			Some((_, other)) => {
+				return Err(format!(
+					"\"CargoManifest\" of paclet {} is not a string: {other}",
+					info.name
+				))
+			},
+			None => match paclet_dir.join("Cargo.toml") {
+				manifest if manifest.is_file() => manifest,
+				_ => return Ok(None),
+			},
+		};
+
+		Ok(Some(LibraryCrate {
+			manifest,
+			resources_dir,
+		}))
+	}
+
+	/// Directory containing the crate.
+	pub fn dir(&self) -> &Path {
+		self.manifest
+			.parent()
+			.expect("Cargo manifest path has no parent directory")
+	}
+
+	/// Build the crate using the Cargo `profile`, and copy the dynamic
+	/// libraries it builds into the `LibraryResources/<$SystemID>/` directory
+	/// of the paclet.
+	///
+	/// `system_id` is the `$SystemID` of the Kernel that will load the
+	/// libraries. An error is returned if Cargo builds libraries for a
+	/// different platform, e.g. if the Kernel is an x86-64 build running under
+	/// Rosetta on an ARM64 Mac.
+	///
+	/// Libraries are only copied if they differ from the library already in
+	/// the paclet, so that unchanged libraries don't trigger a rebuild of a
+	/// watched paclet.
+	pub fn build(&self, profile: &str, system_id: &str) -> Result<(), String> {
+		let target_system_id = target_system_id()?;
+
+		if system_id != target_system_id {
+			return Err(format!(
+				"the Wolfram Kernel $SystemID is {system_id}, but Cargo builds \
+				 libraries for {target_system_id}"
+			));
+		}
+
+		println!(
+			"{} {}",
+			"Building Cargo crate:".bold(),
+			self.manifest.display()
+		);
+
+		let mut child = Command::new("cargo")
+			.arg("build")
+			.arg("--profile")
+			.arg(profile)
+			.arg("--manifest-path")
+			.arg(&self.manifest)
+			// Print compiler diagnostics as usual, and artifact messages as
+			// JSON to stdout.
+			.arg("--message-format=json-render-diagnostics")
+			.stdout(Stdio::piped())
+			.spawn()
+			.map_err(|err| format!("unable to run cargo: {err}"))?;
+
+		let stdout = child.stdout.take().expect("cargo stdout was not piped");
+
+		let libraries = match built_libraries(&self.manifest, stdout) {
+			Ok(libraries) => libraries,
+			Err(err) => {
+				// Don't leave cargo running, or as a zombie process.
+				let _ = child.kill();
+				let _ = child.wait();
+
+				return Err(err);
+			},
+		};
+
+		let status = child
+			.wait()
+			.map_err(|err| format!("error waiting for cargo: {err}"))?;
+
+		if !status.success() {
+			return Err(format!(
+				"cargo build of {} failed ({status})",
+				self.manifest.display()
+			));
+		}
+
+		if libraries.is_empty() {
+			return Err(format!(
+				"cargo build of {} did not build a dynamic library (is the \
+				 crate-type \"cdylib\"?)",
+				self.manifest.display()
+			));
+		}
+
+		let dest_dir = self.resources_dir.join(system_id);
+
+		for library in libraries {
+			let dest = dest_dir.join(library_file_name(&library)?);
+
+			copy_if_changed(&library, &dest)?;
+
+			println!("Library: {}", dest.display());
+		}
+
+		Ok(())
+	}
+}
+
+/// Read the JSON messages printed by `cargo build` and return the dynamic
+/// libraries built by the crate whose manifest is `manifest`.
+fn built_libraries(
+	manifest: &Path,
+	stdout: impl std::io::Read,
+) -> Result<Vec<PathBuf>, String> {
+	let manifest = manifest.canonicalize().map_err(|err| {
+		format!("error reading {}: {err}", manifest.display())
+	})?;
+
+	let mut libraries = Vec::new();
+
+	for line in std::io::BufReader::new(stdout).lines() {
+		let line =
+			line.map_err(|err| format!("error reading cargo output: {err}"))?;
+
+		let Ok(message) = serde_json::from_str::<serde_json::Value>(&line)
+		else {
+			continue;
+		};
+
+		if message["reason"] != "compiler-artifact"
+			|| message["manifest_path"].as_str().map(Path::new)
+				!= Some(manifest.as_path())
+		{
+			continue;
+		}
+
+		let is_dynamic_library =
+			message["target"]["kind"].as_array().is_some_and(|kinds| {
+				kinds.iter().any(|kind| kind == "cdylib" || kind == "dylib")
+			});
+
+		if !is_dynamic_library {
+			continue;
+		}
+
+		let filenames = message["filenames"].as_array().into_iter().flatten();
+
+		libraries.extend(
+			filenames
+				.filter_map(|filename| filename.as_str())
+				.map(PathBuf::from)
+				.filter(|filename| {
+					filename.extension().is_some_and(|extension| {
+						extension == std::env::consts::DLL_EXTENSION
+					})
+				}),
+		);
+	}
+
+	Ok(libraries)
+}
+
+/// Get the name a dynamic library is given in the `LibraryResources` directory.
+///
+/// The same `lib<name>` file name prefix is used on every platform, so that the
+/// library can be loaded using `LibraryFunctionLoad["lib<name>", ..]`.
+fn library_file_name(library: &Path) -> Result<String, String> {
+	let name = library
+		.file_name()
+		.and_then(|name| name.to_str())
+		.ok_or_else(|| {
+			format!("invalid library file name: {}", library.display())
+		})?;
+
+	Ok(match name.starts_with("lib") {
+		true => name.to_owned(),
+		false => format!("lib{name}"),
+	})
+}
+
+fn copy_if_changed(source: &Path, dest: &Path) -> Result<(), String> {
+	let contents = std::fs::read(source)
+		.map_err(|err| format!("error reading {}: {err}", source.display()))?;
+
+	if std::fs::read(dest).is_ok_and(|existing| existing == contents) {
+		return Ok(());
+	}
+
+	if let Some(parent) = dest.parent() {
+		std::fs::create_dir_all(parent).map_err(|err| {
+			format!("error creating directory {}: {err}", parent.display())
+		})?;
+	}
+
+	std::fs::write(dest, contents)
+		.map_err(|err| format!("error writing {}: {err}", dest.display()))
+}
+
+/// Get the Wolfram `$SystemID` of the platform `wolfram-cli` was built for,
+/// which is assumed to be the platform Cargo builds libraries for.
+fn target_system_id() -> Result<&'static str, String> {
+	use std::env::consts::{ARCH, OS};
+
+	match (OS, ARCH) {
+		("macos", "x86_64") => Ok("MacOSX-x86-64"),
+		("macos", "aarch64") => Ok("MacOSX-ARM64"),
+		("linux", "x86_64") => Ok("Linux-x86-64"),
+		("linux", "aarch64") => Ok("Linux-ARM64"),
+		("linux", "arm") => Ok("Linux-ARM"),
+		("windows", "x86_64") => Ok("Windows-x86-64"),
+		_ => Err(format!(
+			"unable to determine the Wolfram $SystemID of platform {OS}-{ARCH}"
+		)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::test_util::TempDir;
+
+	#[test]
+	fn test_library_file_name() {
+		// Windows libraries have no "lib" prefix.
+		assert_eq!(
+			library_file_name(Path::new("C:/target/release/clap_link.dll")),
+			Ok("libclap_link.dll".to_owned())
+		);
+		assert_eq!(
+			library_file_name(Path::new("/target/release/libclap_link.dylib")),
+			Ok("libclap_link.dylib".to_owned())
+		);
+		assert_eq!(
+			library_file_name(Path::new("/target/debug/libclap_link.so")),
+			Ok("libclap_link.so".to_owned())
+		);
+	}
+
+	#[test]
+	fn test_built_libraries() {
+		let dir = TempDir::new("cargo-built-libraries");
+		let manifest = dir.write("my-link/Cargo.toml", "");
+		let other = dir.write("other/Cargo.toml", "");
+
+		let library = format!(
+			"/target/release/libmy_link.{}",
+			std::env::consts::DLL_EXTENSION
+		);
+
+		let message = |manifest: &Path, kind: &str, filenames: &[&str]| {
+			serde_json::json!({
+				"reason": "compiler-artifact",
+				"manifest_path": manifest,
+				"target": { "kind": [kind] },
+				"filenames": filenames,
+			})
+			.to_string()
+		};
+
+		let output = [
+			"Not JSON".to_owned(),
+			message(&other, "cdylib", &["/target/release/libother.so"]),
+			message(&manifest, "lib", &["/target/release/libmy_link.rlib"]),
+			message(
+				&manifest,
+				"cdylib",
+				&[&library, "/target/release/my_link.d"],
+			),
+			serde_json::json!({ "reason": "build-finished", "success": true })
+				.to_string(),
+		]
+		.join("\n");
+
+		assert_eq!(
+			built_libraries(&manifest, output.as_bytes()),
+			Ok(vec![PathBuf::from(&library)])
+		);
+	}
+}
@@ -0,0 +1,246 @@
+//! Rendering of the differences between the expected and actual values of
+//! failed tests.
+
+use colored::{ColoredString, Colorize};
+use similar::{ChangeTag, DiffOp, TextDiff};
+
+/// Width used when the width of the terminal can't be determined, e.g.
+/// because output is being redirected to a file.
+const DEFAULT_WIDTH: usize = 100;
+
+/// Minimum terminal width at which the [`DiffStyle::Split`] layout is used.
+/// In narrower terminals the [`DiffStyle::Unified`] layout is used instead.
+const MIN_SPLIT_WIDTH: usize = 60;
+
+/// Separator printed between the two columns of a split diff.
+const SPLIT_SEPARATOR: &str = " │ ";
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[derive(clap::ValueEnum)]
+pub enum DiffStyle {
+	/// Show removed and added lines one after another.
+	#[default]
+	Unified,
+	/// Show expected and actual lines side by side.
+	Split,
+	/// Don't show diffs.
+	None,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct DiffOptions {
+	pub style: DiffStyle,
+	/// Lines of unchanged context to show around each change.
+	pub context: usize,
+}
+
+/// A line of a diff, made up of segments of text that are emphasized if they
+/// are the part of the line that changed.
+type Line<'s> = Vec<(bool, &'s str)>;
+
+/// Print the differences between `expected` and `actual`.
+pub fn print_diff(expected: &str, actual: &str, options: &DiffOptions) {
+	let DiffOptions { style, context } = *options;
+
+	let width = terminal_width();
+
+	let style = match style {
+		DiffStyle::Split if width < MIN_SPLIT_WIDTH => DiffStyle::Unified,
+		style => style,
+	};
+
+	if style == DiffStyle::None {
+		return;
+	}
+
+	let diff = TextDiff::from_lines(expected, actual);
+
+	for hunk in diff.unified_diff().context_radius(context).iter_hunks() {
+		println!("{}", hunk.header().to_string().cyan());
+
+		for op in hunk.ops() {
+			match style {
+				DiffStyle::Unified => print_unified_op(&diff, op),
+				DiffStyle::Split => print_split_op(&diff, op, width),
+				DiffStyle::None => unreachable!(),
+			}
+		}
+	}
+}
+
+fn print_unified_op<'s>(diff: &'s TextDiff<'s, 's, 's, str>, op: &DiffOp) {
+	for change in diff.iter_inline_changes(op) {
+		let tag = change.tag();
+
+		let sign = match tag {
+			ChangeTag::Equal => "  ",
+			ChangeTag::Delete => "- ",
+			ChangeTag::Insert => "+ ",
+		};
+
+		print!("{}", style(tag, false, sign));
+
+		for (emphasized, value) in change.iter_strings_lossy() {
+			print!("{}", style(tag, emphasized, value.trim_end_matches('\n')));
+		}
+
+		println!();
+	}
+}
+
+fn print_split_op<'s>(
+	diff: &'s TextDiff<'s, 's, 's, str>,
+	op: &DiffOp,
+	width: usize,
+) {
+	let column_width = (width - SPLIT_SEPARATOR.chars().count()) / 2;
+
+	let mut expected: Vec<(ChangeTag, Line)> = Vec::new();
+	let mut actual: Vec<(ChangeTag, Line)> = Vec::new();
+
+	for change in diff.iter_inline_changes(op) {
+		let line: Line = change
+			.values()
+			.iter()
+			.map(|(emphasized, value)| {
+				(*emphasized, value.trim_end_matches('\n'))
+			})
+			.collect();
+
+		match change.tag() {
+			ChangeTag::Equal => {
+				expected.push((ChangeTag::Equal, line.clone()));
+				actual.push((ChangeTag::Equal, line));
+			},
+			ChangeTag::Delete => expected.push((ChangeTag::Delete, line)),
+			ChangeTag::Insert => actual.push((ChangeTag::Insert, line)),
+		}
+	}
+
+	for row in 0..expected.len().max(actual.len()) {
+		let left = expected
+			.get(row)
+			.map(|(tag, line)| (*tag, wrap(line, column_width)));
+		let right = actual
+			.get(row)
+			.map(|(tag, line)| (*tag, wrap(line, column_width)));
+
+		let rows = |side: &Option<(ChangeTag, Vec<Line>)>| {
+			side.as_ref().map_or(0, |(_, rows)| rows.len())
+		};
+
+		// Print each wrapped row of the expected and actual lines.
+		for index in 0..rows(&left).max(rows(&right)) {
+			let printed = print_column(&left, index);
+			let padding = column_width.saturating_sub(printed);
+
+			print!("{:padding$}{}", "", SPLIT_SEPARATOR.dimmed());
+			print_column(&right, index);
+			println!();
+		}
+	}
+}
+
+/// Print the `index`-th wrapped row of one side of a split diff, returning the
+/// number of characters printed.
+fn print_column(side: &Option<(ChangeTag, Vec<Line>)>, index: usize) -> usize {
+	let mut printed = 0;
+
+	if let Some((tag, rows)) = side {
+		if let Some(row) = rows.get(index) {
+			for (emphasized, value) in row {
+				print!("{}", style(*tag, *emphasized, value));
+				printed += value.chars().count();
+			}
+		}
+	}
+
+	printed
+}
+
+/// Split `line` into rows that are at most `width` characters wide.
+fn wrap<'s>(line: &Line<'s>, width: usize) -> Vec<Line<'s>> {
+	let mut rows = vec![Vec::new()];
+	let mut row_width = 0;
+
+	for &(emphasized, mut value) in line {
+		while !value.is_empty() {
+			if row_width == width {
+				rows.push(Vec::new());
+				row_width = 0;
+			}
+
+			let split = value
+				.char_indices()
+				.nth(width - row_width)
+				.map_or(value.len(), |(index, _)| index);
+
+			let (head, rest) = value.split_at(split);
+
+			rows.last_mut().unwrap().push((emphasized, head));
+			row_width += head.chars().count();
+			value = rest;
+		}
+	}
+
+	rows
+}
+
+fn style(tag: ChangeTag, emphasized: bool, text: &str) -> ColoredString {
+	match (tag, emphasized) {
+		(ChangeTag::Equal, _) => text.normal(),
+		(ChangeTag::Delete, false) => text.red(),
+		(ChangeTag::Delete, true) => text.red().bold().underline(),
+		(ChangeTag::Insert, false) => text.green(),
+		(ChangeTag::Insert, true) => text.green().bold().underline(),
+	}
+}
+
+fn terminal_width() -> usize {
+	match terminal_size::terminal_size() {
+		Some((terminal_size::Width(width), _)) => usize::from(width),
+		None => DEFAULT_WIDTH,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_wrap() {
+		// Lines that fit aren't wrapped.
+		assert_eq!(wrap(&vec![(false, "abc")], 5), vec![vec![(false, "abc")]]);
+		assert_eq!(
+			wrap(&vec![(false, "abcde")], 5),
+			vec![vec![(false, "abcde")]]
+		);
+
+		assert_eq!(
+			wrap(&vec![(false, "abcdefghijk")], 5),
+			vec![
+				vec![(false, "abcde")],
+				vec![(false, "fghij")],
+				vec![(false, "k")],
+			]
+		);
+
+		// Segments are split across rows, keeping their emphasis.
+		assert_eq!(
+			wrap(&vec![(false, "abc"), (true, "defg"), (false, "h")], 5),
+			vec![
+				vec![(false, "abc"), (true, "de")],
+				vec![(true, "fg"), (false, "h")],
+			]
+		);
+
+		// Widths are measured in characters, not bytes.
+		assert_eq!(
+			wrap(&vec![(false, "αβγδε")], 2),
+			vec![vec![(false, "αβ")], vec![(false, "γδ")], vec![(false, "ε")],]
+		);
+
+		// Empty lines have a single empty row.
+		assert_eq!(wrap(&vec![], 5), vec![Vec::<(bool, &str)>::new()]);
+	}
+}
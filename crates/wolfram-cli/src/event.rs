@@ -0,0 +1,48 @@
+//! Structured events sent to the client by the WolframCLI paclet.
+//!
+//! Events are printed by the Kernel as a single line of output containing
+//! [`PREFIX`] followed by the event data encoded as JSON. See
+//! `printClientEvent[..]` in `WolframCLI.wl`.
+
+use serde::Deserialize;
+
+/// Prefix of a line of Kernel output that contains an event.
+pub const PREFIX: &str = "wolfram-cli-event: ";
+
+#[derive(Debug)]
+#[derive(Deserialize)]
+#[serde(tag = "Event")]
+pub enum Event {
+	/// The expected and actual values of a failed test, formatted as
+	/// Wolfram Language code.
+	#[serde(rename_all = "PascalCase")]
+	TestDiff { expected: String, actual: String },
+	/// The test files to be run have been discovered. `test_count` is the
+	/// number of tests the files appear to contain.
+	#[serde(rename_all = "PascalCase")]
+	RunStarted {
+		file_count: usize,
+		test_count: usize,
+	},
+	/// The tests in `file` are about to be run.
+	#[serde(rename_all = "PascalCase")]
+	FileStarted { file: String },
+	/// A test finished with the specified outcome, e.g. `"Success"` or
+	/// `"Failure"`.
+	#[serde(rename_all = "PascalCase")]
+	TestFinished { outcome: String },
+}
+
+/// Parse an event from a line of Kernel output, returning `None` if `text`
+/// is ordinary output.
+pub fn parse(text: &str) -> Option<Event> {
+	let json = text.strip_prefix(PREFIX)?;
+
+	match serde_json::from_str(json.trim_end()) {
+		Ok(event) => Some(event),
+		Err(err) => {
+			eprintln!("warning: unable to parse event sent by Kernel: {err}");
+			None
+		},
+	}
+}
@@ -1,4 +1,4 @@
-use wolfram_app_discovery::WolframApp;
+use wolfram_app_discovery::{WolframApp, WolframVersion};
 use wolfram_client::WolframSession;
 
 use crate::config;
@@ -6,8 +6,11 @@ use crate::config;
 const WOLFRAM_MINIMUM_VERSION: (u32, u32) = (13, 1);
 
 pub fn launch_kernel() -> WolframSession {
-	let app = get_wolfram_app();
+	launch_kernel_from_app(&get_wolfram_app())
+}
 
+/// Launch the WolframKernel of a specific Wolfram Language installation.
+pub fn launch_kernel_from_app(app: &WolframApp) -> WolframSession {
 	let exe = app.kernel_executable_path().unwrap();
 
 	let mut session = WolframSession::launch_kernel(&exe)
@@ -23,6 +26,35 @@ pub fn launch_kernel() -> WolframSession {
 	session
 }
 
+/// Find all Wolfram Language installations compatible with `wolfram-cli`,
+/// ordered from oldest to newest version.
+pub fn discover_apps() -> Vec<WolframApp> {
+	let mut apps: Vec<(WolframVersion, WolframApp)> =
+		wolfram_app_discovery::discover()
+			.into_iter()
+			.filter_map(|app| {
+				let version = app.wolfram_version().ok()?;
+
+				is_supported_version(&version).then_some((version, app))
+			})
+			.collect();
+
+	apps.sort_by_key(|(version, _)| {
+		(version.major(), version.minor(), version.patch())
+	});
+
+	if config::verbosity() >= 1 {
+		for (_, app) in &apps {
+			eprintln!(
+				"verbose: Discovered Wolfram installation at: {}",
+				app.installation_directory().display()
+			);
+		}
+	}
+
+	apps.into_iter().map(|(_, app)| app).collect()
+}
+
 /// Find a suitable Wolfram Language installation
 fn get_wolfram_app() -> WolframApp {
 	let app = WolframApp::try_default()
@@ -37,10 +69,7 @@ fn get_wolfram_app() -> WolframApp {
 
 	let wolfram_version = app.wolfram_version().unwrap();
 
-	if wolfram_version.major() < WOLFRAM_MINIMUM_VERSION.0
-		|| (wolfram_version.major() == WOLFRAM_MINIMUM_VERSION.0
-			&& wolfram_version.minor() < WOLFRAM_MINIMUM_VERSION.1)
-	{
+	if !is_supported_version(&wolfram_version) {
 		panic!(
             "incompatible Wolfram version: {wolfram_version}. {}.{} or newer is required for this command.",
             WOLFRAM_MINIMUM_VERSION.0, WOLFRAM_MINIMUM_VERSION.1
@@ -49,3 +78,118 @@ fn get_wolfram_app() -> WolframApp {
 
 	app
 }
+
+fn is_supported_version(version: &WolframVersion) -> bool {
+	(version.major(), version.minor()) >= WOLFRAM_MINIMUM_VERSION
+}
+
+/// Whether `version` satisfies a paclet `"WolframVersion"` specification.
+///
+/// A specification is a comma-separated list of alternatives, each of which
+/// is one of:
+///
+/// * `*` — any version
+/// * `13.1+` — version 13.1 or newer
+/// * `12.3-13.1` — any version from 12.3 through 13.1
+/// * `13.1` or `13.1.*` — any 13.1.x version
+pub fn version_satisfies(spec: &str, version: &WolframVersion) -> bool {
+	let version = [version.major(), version.minor(), version.patch()];
+
+	// Compare `version` against only as many components as `bound` specifies,
+	// so that e.g. "13" and "13.*" match every 13.x.y version.
+	let compare = |bound: &str| -> Option<std::cmp::Ordering> {
+		let bound = bound.trim();
+		let bound = bound.strip_suffix(".*").unwrap_or(bound);
+
+		let bound: Vec<u32> = bound
+			.split('.')
+			.map(|part| part.parse().ok())
+			.collect::<Option<_>>()?;
+
+		let len = bound.len().min(version.len());
+
+		Some(version[..len].cmp(&bound[..len]))
+	};
+
+	spec.split(',').map(str::trim).any(|alternative| {
+		if alternative == "*" {
+			return true;
+		}
+
+		if let Some(min) = alternative.strip_suffix('+') {
+			return compare(min).is_some_and(|ord| ord.is_ge());
+		}
+
+		if let Some((min, max)) = alternative.split_once('-') {
+			return compare(min).is_some_and(|ord| ord.is_ge())
+				&& compare(max).is_some_and(|ord| ord.is_le());
+		}
+
+		compare(alternative).is_some_and(|ord| ord.is_eq())
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn satisfies(spec: &str, (major, minor, patch): (u32, u32, u32)) -> bool {
+		version_satisfies(spec, &WolframVersion::new(major, minor, patch))
+	}
+
+	#[test]
+	fn test_version_satisfies_minimum() {
+		assert!(satisfies("13+", (13, 0, 0)));
+		assert!(satisfies("13+", (14, 1, 0)));
+		assert!(!satisfies("13+", (12, 3, 1)));
+
+		assert!(satisfies("13.1+", (13, 1, 0)));
+		assert!(satisfies("13.1+", (13, 2, 0)));
+		assert!(!satisfies("13.1+", (13, 0, 1)));
+	}
+
+	#[test]
+	fn test_version_satisfies_wildcard() {
+		assert!(satisfies("*", (12, 0, 0)));
+
+		assert!(satisfies("13.1.*", (13, 1, 0)));
+		assert!(satisfies("13.1.*", (13, 1, 2)));
+		assert!(!satisfies("13.1.*", (13, 2, 0)));
+		assert!(!satisfies("13.1.*", (12, 1, 0)));
+
+		assert!(satisfies("13.*", (13, 3, 0)));
+		assert!(!satisfies("13.*", (14, 0, 0)));
+	}
+
+	#[test]
+	fn test_version_satisfies_exact() {
+		assert!(satisfies("13.1", (13, 1, 0)));
+		assert!(satisfies("13.1", (13, 1, 3)));
+		assert!(!satisfies("13.1", (13, 2, 0)));
+
+		assert!(satisfies("13.1.2", (13, 1, 2)));
+		assert!(!satisfies("13.1.2", (13, 1, 3)));
+	}
+
+	#[test]
+	fn test_version_satisfies_range_and_alternatives() {
+		assert!(satisfies("12.3-13.1", (12, 3, 0)));
+		assert!(satisfies("12.3-13.1", (13, 1, 4)));
+		assert!(!satisfies("12.3-13.1", (13, 2, 0)));
+
+		assert!(satisfies("12.0, 13.2+", (12, 0, 1)));
+		assert!(satisfies("12.0, 13.2+", (14, 0, 0)));
+		assert!(!satisfies("12.0, 13.2+", (13, 1, 0)));
+	}
+
+	#[test]
+	fn test_version_satisfies_invalid() {
+		for spec in ["", "abc", "13.x", "13++", "+", "13.1-", "-13.1", "13..1"]
+		{
+			assert!(
+				!satisfies(spec, (13, 1, 0)),
+				"invalid spec {spec:?} should not be satisfied"
+			);
+		}
+	}
+}
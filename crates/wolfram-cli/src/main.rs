@@ -1,14 +1,36 @@
+mod build_cache;
+mod cargo;
 mod config;
+mod diff;
+mod event;
 mod kernel;
-
-
-use std::{io::Write, path::PathBuf, str::FromStr};
+mod paclet_info;
+mod paths;
+mod progress;
+mod template;
+#[cfg(test)]
+mod test_util;
+mod watch;
+mod workspace;
+
+
+use std::{
+	io::Write,
+	path::{Path, PathBuf},
+	str::FromStr,
+};
 
 use clap::Parser;
 use colored::Colorize;
 
 use wolfram_client::{EvaluationOutcome, Packet, PacketExpr, WolframSession};
-use wolfram_expr::{Expr, Symbol};
+use wolfram_expr::{Expr, ExprKind, Symbol};
+
+use crate::{
+	diff::{DiffOptions, DiffStyle},
+	paths::PathKind,
+	progress::Progress,
+};
 
 //==========================================================
 // CLI Argument Declarations
@@ -46,6 +68,18 @@ enum Command {
 	#[command(subcommand)]
 	Paclet(PacletCommand),
 
+	/// Run test files that are not part of a paclet
+	///
+	/// Each specified `.wlt` or `.mt` file, and each such file in the
+	/// specified directories, is run using MUnit. `Setup.wl` and
+	/// `Teardown.wl` fixture files are run as for `$ wolfram-cli paclet test`.
+	///
+	/// The time taken, peak memory used, and number of messages issued by
+	/// each test file are recorded in the `build/wolfram-cli/test-report.json`
+	/// file in the current directory. Messages silenced using `Quiet[..]` or
+	/// `Off[..]` are not counted.
+	Test(TestArgs),
+
 	#[clap(hide = true)]
 	PrintAllHelp {
 		#[clap(long, required = true)]
@@ -64,25 +98,39 @@ enum PacletCommand {
 	/// Create a new paclet in the current directory with the specified name.
 	New {
 		/// Name of the paclet
-		name: String,
+		///
+		/// Paclet names have the form `Publisher/BaseName` or `BaseName`, where
+		/// the publisher and base name start with a letter and contain only
+		/// letters and digits.
+		#[arg(value_parser = PacletName::from_str)]
+		name: PacletName,
 		#[arg(
 			long = "base",
 			short = 'b',
 			help = "use paclet base name as directory name"
 		)]
 		shorten_to_base_name: bool,
+		/// Template to create the paclet from
+		///
+		/// The built-in templates are:
+		///
+		/// * `library` — a paclet containing a Wolfram Language package
+		/// * `cli` — a paclet that adds a subcommand to `wolfram-cli`
+		/// * `rust` — a paclet containing a Rust LibraryLink library
+		///
+		/// Alternatively, specify the path to a directory containing template
+		/// files. Occurrences of `{{PacletName}}`, `{{Publisher}}`,
+		/// `{{BaseName}}`, `{{Context}}`, `{{SnakeCaseName}}` and
+		/// `{{KebabCaseName}}` in the contents and names of the template files
+		/// are replaced with the corresponding values for the new paclet.
+		#[arg(long, short, value_name = "TEMPLATE", default_value = "library")]
+		template: String,
 	},
 	/// Build the specified paclet
 	///
 	/// This uses [`PacletBuild[..]`](https://reference.wolfram.com/language/PacletTools/ref/PacletBuild)
 	/// to build the specified paclet.
-	Build {
-		paclet_dir: Option<PathBuf>,
-		build_dir: Option<PathBuf>,
-		/// Install the built paclet.
-		#[arg(short, long)]
-		install: bool,
-	},
+	Build(PacletBuildArgs),
 	/// Build paclet documentation
 	Doc {
 		paclet_dir: Option<PathBuf>,
@@ -93,11 +141,20 @@ enum PacletCommand {
 		/// Automatically open the built HTML documentation
 		#[arg(long, requires = "html")]
 		open: bool,
+		/// Build the documentation of every paclet in the workspace containing
+		/// the current directory, in dependency order.
+		#[arg(long, conflicts_with_all = ["paclet_dir", "build_dir"])]
+		workspace: bool,
 	},
 	/// Install the specified `.paclet` file
 	///
 	/// This uses [`PacletInstall`] to install the specified paclet archive file.
 	///
+	/// If a paclet directory is specified, the paclet is built and then
+	/// installed. If the name of a paclet is specified, the newest version of
+	/// the paclet (or the specified version) is installed from the registered
+	/// paclet sites that are local directories.
+	///
 	/// [`PacletInstall`]: https://reference.wolfram.com/language/ref/PacletInstall
 	///
 	/// ### CLI Examples
@@ -107,29 +164,386 @@ enum PacletCommand {
 	/// ```shell
 	/// $ wolfram-cli paclet install MyPaclet.paclet
 	/// ```
-	Install { paclet_file: PathBuf },
+	///
+	/// Install version 1.0.0 of a paclet from a local paclet site:
+	///
+	/// ```shell
+	/// $ wolfram-cli paclet install MyPublisher/MyPaclet@1.0.0
+	/// ```
+	Install {
+		/// `.paclet` file, paclet directory, or paclet name of the form
+		/// `NAME` or `NAME@VERSION`
+		#[arg(value_name = "PACLET")]
+		paclet: String,
+		/// Install the paclet even if a newer version of it is already
+		/// installed. This is the default.
+		#[arg(long, overrides_with = "no_force_version")]
+		force_version: bool,
+		/// Don't install the paclet if a newer version of it is already
+		/// installed.
+		#[arg(long, overrides_with = "force_version")]
+		no_force_version: bool,
+		/// Print the paclet that would be installed, without building or
+		/// installing it.
+		#[arg(long)]
+		dry_run: bool,
+		/// Print the installed paclet as JSON.
+		#[arg(long)]
+		json: bool,
+	},
+	/// List installed paclets
+	///
+	/// This lists every paclet known to the paclet manager, including
+	/// disabled paclets, with its version, location, and whether it is
+	/// enabled, loaded, or shadowed by another paclet with the same name that
+	/// takes precedence over it.
+	List {
+		/// Only list paclets whose name matches PATTERN.
+		///
+		/// `*` in the pattern matches any sequence of characters, e.g.
+		/// `Wolfram/*`. Matching ignores case.
+		pattern: Option<String>,
+		/// Print the list of paclets as JSON.
+		#[arg(long)]
+		json: bool,
+	},
+	/// Uninstall a paclet
+	///
+	/// This uses [`PacletUninstall`] to uninstall every installed version of
+	/// the named paclet, or only the specified version.
+	///
+	/// [`PacletUninstall`]: https://reference.wolfram.com/language/ref/PacletUninstall
+	///
+	/// ### CLI Examples
+	///
+	/// Uninstall version 1.0.0 of a paclet, without asking for confirmation:
+	///
+	/// ```shell
+	/// $ wolfram-cli paclet uninstall MyPublisher/MyPaclet@1.0.0 --yes
+	/// ```
+	Uninstall {
+		/// Paclet to uninstall, in the form `NAME` or `NAME@VERSION`
+		#[arg(value_parser = PacletSpec::from_str, value_name = "NAME[@VERSION]")]
+		paclet: PacletSpec,
+		/// Uninstall without asking for confirmation.
+		#[arg(short, long)]
+		yes: bool,
+	},
 	/// Run tests defined for a paclet
 	///
 	/// This uses `` PacletTools`PacletTest `` to execute any tests defined by
 	/// the specified paclet.
+	///
+	/// If the directory containing a test file has a `Setup.wl` or
+	/// `Teardown.wl` file, it is run before or after the tests in the file.
+	/// A test file can also name its own fixture files using
+	/// `(* :Setup: path *)` and `(* :Teardown: path *)` comments, with paths
+	/// relative to the test file. Teardown files are run even if the tests
+	/// fail, and a failed fixture file counts as a test failure.
+	///
+	/// The time taken, peak memory used, and number of messages issued by
+	/// each test file are recorded in the `build/wolfram-cli/test-report.json`
+	/// file in the paclet directory. Messages silenced using `Quiet[..]` or
+	/// `Off[..]` are not counted.
 	//
 	// TODO: PacletTest is undocumented. Once it is, include a link to it above.
-	Test {
-		/// Optional path to a paclet directory.
-		///
-		/// This should be a directory containing a `PacletInfo.wl` file.
+	Test(PacletTestArgs),
+	/// Show the metadata of a paclet
+	///
+	/// This reads the `PacletInfo.wl` file of a paclet directory or `.paclet`
+	/// archive, and prints the name, version, publisher, required Wolfram
+	/// version, dependencies, and extensions of the paclet, along with the
+	/// files provided by each extension.
+	///
+	/// The metadata is read without launching a Kernel.
+	Info {
+		/// Paclet directory or `.paclet` archive file.
 		///
-		/// This no paclet directory is specified, the current directory is
-		/// the default.
-		paclet_dir: Option<PathBuf>,
+		/// If no paclet is specified, the current directory is the default.
+		paclet: Option<PathBuf>,
+		/// Print the metadata as JSON.
+		#[arg(long)]
+		json: bool,
+	},
+	/// Run benchmarks defined for a paclet
+	///
+	/// Benchmarks are `.wl` files in the `"Benchmarks"` extension directory
+	/// of the paclet (or the `Benchmarks` directory, if the paclet declares
+	/// no such extension) containing `Benchmark[name, expr]` expressions.
+	/// Other expressions in a benchmark file are evaluated normally, and can
+	/// be used to set up the benchmarks that follow them.
+	///
+	/// Each benchmark file is run in a fresh Kernel. The timing and memory
+	/// use of each benchmark are compared against the previous benchmark run,
+	/// or against a saved baseline, and the command fails if any benchmark
+	/// regressed or any benchmark file could not be run.
+	Bench(PacletBenchArgs),
+}
+
+#[derive(Debug)]
+#[derive(clap::Args)]
+struct PacletBuildArgs {
+	paclet_dir: Option<PathBuf>,
+	build_dir: Option<PathBuf>,
+	/// Install the built paclet.
+	#[arg(short, long)]
+	install: bool,
+	/// Build in a kernel with a fresh, temporary user base directory, in
+	/// which only the paclet's declared dependencies are installed.
+	///
+	/// The kernel is also launched with `-noinit`, so `init.m` files are
+	/// not run.
+	#[arg(long)]
+	isolated: bool,
+	/// Watch the paclet directory, and rebuild the paclet when its files
+	/// change.
+	///
+	/// The same Kernel is used for each rebuild, and the outcome of each
+	/// rebuild is printed as a single status line. If `--install` is
+	/// specified, the paclet is reinstalled after each successful rebuild.
+	#[arg(short, long)]
+	watch: bool,
+	/// Build the paclet even if its files and the build options are
+	/// unchanged since the last successful build.
+	///
+	/// By default, the paclet is not rebuilt if nothing changed, and the
+	/// `.paclet` archive built previously is used instead.
+	#[arg(long)]
+	force: bool,
+	/// Cargo profile used to build the Rust LibraryLink crate of the
+	/// paclet, if it has one.
+	///
+	/// A paclet declares a crate using the `"CargoManifest"` option of
+	/// its `"LibraryResources"` extension, or by having a `Cargo.toml`
+	/// file in the paclet directory. The crate is built before the paclet,
+	/// and the dynamic library it builds is copied into the
+	/// `LibraryResources/<$SystemID>/` directory of the paclet.
+	#[arg(long, value_name = "PROFILE", default_value = "release")]
+	cargo_profile: String,
+	/// Build every paclet in the workspace containing the current
+	/// directory, in dependency order.
+	///
+	/// The workspace is described by a `wolfram-cli-workspace.toml` file
+	/// listing the paclet directories that are members of the workspace.
+	#[arg(long, conflicts_with_all = ["paclet_dir", "build_dir", "watch"])]
+	workspace: bool,
+}
+
+#[derive(Debug)]
+#[derive(clap::Args)]
+struct PacletBenchArgs {
+	/// Optional path to a paclet directory.
+	paclet_dir: Option<PathBuf>,
 
-		/// Optional file or directory containing tests to be run.
-		tests_path: Option<PathBuf>,
+	/// Optional benchmark file, or directory containing benchmark files, to
+	/// be run.
+	bench_path: Option<PathBuf>,
+
+	/// Number of times to evaluate each benchmark before measuring it.
+	#[arg(long, value_name = "N", default_value_t = 3)]
+	warmup: u32,
+
+	/// Number of times to evaluate and measure each benchmark.
+	#[arg(
+		long,
+		short = 'n',
+		value_name = "N",
+		default_value_t = 10,
+		value_parser = clap::value_parser!(u32).range(1..)
+	)]
+	repetitions: u32,
+
+	/// Compare against the baseline saved with `--save-baseline <NAME>`,
+	/// instead of against the previous benchmark run.
+	#[arg(long, value_name = "NAME")]
+	baseline: Option<String>,
+
+	/// Save the results of this benchmark run as a baseline named NAME.
+	///
+	/// The results of each benchmark run are recorded in the
+	/// `build/wolfram-cli/benchmarks/` directory of the paclet.
+	#[arg(long, value_name = "NAME")]
+	save_baseline: Option<String>,
+
+	/// Percentage by which the median time of a benchmark must increase to
+	/// be reported as a regression.
+	#[arg(long, value_name = "PERCENT", default_value_t = 10.0)]
+	threshold: f64,
+}
 
-		/// Lines of context to print before and after a diff in test output.
-		#[arg(long, short = 'C')]
-		diff_context: Option<usize>,
-	},
+#[derive(Debug)]
+#[derive(clap::Args)]
+struct PacletTestArgs {
+	/// Optional path to a paclet directory.
+	///
+	/// This should be a directory containing a `PacletInfo.wl` file.
+	///
+	/// This no paclet directory is specified, the current directory is
+	/// the default.
+	paclet_dir: Option<PathBuf>,
+
+	/// Optional file or directory containing tests to be run.
+	tests_path: Option<PathBuf>,
+
+	#[command(flatten)]
+	run: TestRunArgs,
+
+	/// Watch the paclet and test files, and re-run tests when they change.
+	#[arg(long, short = 'w')]
+	watch: bool,
+
+	/// Which tests to re-run when a file changes in `--watch` mode.
+	#[arg(long, value_enum, default_value_t, requires = "watch")]
+	rerun: WatchRerun,
+
+	/// Record which definitions and lines in the paclet Kernel sources are
+	/// evaluated by the tests.
+	///
+	/// A coverage summary for each source file is printed after the tests
+	/// have run, and the coverage data is written in lcov format to the
+	/// `build/wolfram-cli/lcov.info` file in the paclet directory.
+	///
+	/// Only the bodies of delayed definitions (e.g. `f[x_] := ...`) are
+	/// instrumented. Top-level code that is evaluated when a source file is
+	/// loaded is not instrumented, so it isn't included in the line coverage.
+	#[arg(long)]
+	coverage: bool,
+
+	/// Disable installed copies of the paclet that would take precedence over
+	/// the paclet directory during the test run.
+	///
+	/// The tests are run in a kernel with a temporary user base directory, in
+	/// which the other installed paclets are available, so the installed
+	/// copies are only disabled for the test run.
+	///
+	/// Without this option, the test run fails if an installed copy of the
+	/// paclet would be loaded instead of the paclet directory being tested.
+	#[arg(long, visible_alias = "isolate")]
+	disable_installed: bool,
+
+	/// Run tests in a kernel with a fresh, temporary user base directory, in
+	/// which only the paclet's declared dependencies are installed.
+	///
+	/// The kernel is also launched with `-noinit`, so `init.m` files are not
+	/// run.
+	#[arg(long)]
+	isolated: bool,
+
+	/// Run the tests once with each of the specified Wolfram versions.
+	///
+	/// Each version (e.g. `13.2`) selects the discovered Wolfram installations
+	/// with a matching version number. Installations that don't satisfy the
+	/// `"WolframVersion"` field of the paclet are skipped.
+	#[arg(
+		long,
+		value_name = "VERSIONS",
+		value_delimiter = ',',
+		conflicts_with_all = ["all_kernels", "watch"]
+	)]
+	kernels: Vec<String>,
+
+	/// Run the tests once with each discovered Wolfram installation that
+	/// satisfies the `"WolframVersion"` field of the paclet.
+	#[arg(long, conflicts_with = "watch")]
+	all_kernels: bool,
+
+	/// Run the tests of every paclet in the workspace containing the current
+	/// directory, in dependency order.
+	///
+	/// Every paclet in the workspace is loaded while each paclet is tested.
+	#[arg(
+		long,
+		conflicts_with_all = ["paclet_dir", "tests_path", "watch", "kernels", "all_kernels"]
+	)]
+	workspace: bool,
+}
+
+#[derive(Debug)]
+#[derive(clap::Args)]
+struct TestArgs {
+	/// Test files or directories containing test files to be run.
+	#[arg(required = true)]
+	tests_paths: Vec<PathBuf>,
+
+	/// Directory containing packages or paclets to make available to the
+	/// tests.
+	///
+	/// The directory is added to `$Path` and loaded using
+	/// `PacletDirectoryLoad`. May be specified more than once.
+	#[arg(long, value_name = "DIR")]
+	load: Vec<PathBuf>,
+
+	#[command(flatten)]
+	run: TestRunArgs,
+}
+
+/// Options shared by `$ wolfram-cli paclet test` and `$ wolfram-cli test`.
+#[derive(Debug)]
+#[derive(clap::Args)]
+struct TestRunArgs {
+	/// Lines of context to print before and after a diff in test output.
+	#[arg(long, short = 'C', default_value_t = 10)]
+	diff_context: usize,
+
+	/// How to show the differences between the expected and actual values of
+	/// failed tests.
+	///
+	/// The `split` layout falls back to `unified` if the terminal is too
+	/// narrow to show two columns.
+	#[arg(long, value_enum, default_value_t)]
+	diff_style: DiffStyle,
+
+	/// Run only the tests that failed in the previous test run.
+	///
+	/// The outcome of each test is recorded in the
+	/// `build/wolfram-cli/test-results.json` file in the paclet directory, or
+	/// in the current directory for `$ wolfram-cli test`. If no failed tests
+	/// were recorded, all tests are run.
+	#[arg(long, conflicts_with = "failed_first")]
+	failed: bool,
+
+	/// Run the test files containing tests that failed in the previous test
+	/// run before any other test files.
+	#[arg(long)]
+	failed_first: bool,
+
+	/// Print the N slowest tests after the test run summary.
+	///
+	/// The time and memory used by each test are also recorded in the
+	/// `build/wolfram-cli/test-results.json` file.
+	#[arg(long, value_name = "N")]
+	durations: Option<usize>,
+
+	/// Update the expected output of failing snapshot tests.
+	///
+	/// Snapshot tests are `VerificationTest[..]`'s whose `TestID` starts with
+	/// `"Snapshot:"`. When a snapshot test fails, the expected output argument
+	/// in the test file is replaced with the actual output of the test.
+	#[arg(long)]
+	update_snapshots: bool,
+
+	/// Run the test files in a random order.
+	///
+	/// The tests within a file are also run in a random order if the file
+	/// contains only tests that have a `TestID`. The seed used is printed in
+	/// the test run summary.
+	#[arg(long)]
+	shuffle: bool,
+
+	/// Seed for the random order used by `--shuffle`.
+	#[arg(long, value_name = "N", requires = "shuffle")]
+	seed: Option<u32>,
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+#[derive(clap::ValueEnum)]
+enum WatchRerun {
+	/// Re-run only the test files that changed. If any non-test file changed,
+	/// re-run all tests.
+	#[default]
+	Affected,
+	/// Re-run all tests, running any test files that changed first.
+	All,
 }
 
 //==========================================================
@@ -140,6 +554,14 @@ fn main() {
 	let args = match Cli::try_parse() {
 		Ok(args) => args,
 		Err(error) => {
+			// An invalid value for an argument of a built-in subcommand, like
+			// an invalid paclet name passed to `$ wolfram-cli paclet new`,
+			// can't be handled by a custom subcommand, so report it without
+			// launching a Kernel.
+			if error.kind() == clap::error::ErrorKind::ValueValidation {
+				error.exit();
+			}
+
 			// FIXME:
 			//   Only defer to custom subcommand handlers if it was a top-level
 			//   subcommand that doesn't exist in `Cli`.
@@ -184,6 +606,7 @@ fn main() {
 		Command::Paclet(paclet_command) => {
 			handle_paclet_command(paclet_command)
 		},
+		Command::Test(args) => handle_test(args),
 		Command::PrintAllHelp { markdown } => {
 			assert!(markdown);
 
@@ -200,26 +623,34 @@ fn handle_paclet_command(command: PacletCommand) {
 		PacletCommand::New {
 			shorten_to_base_name,
 			name,
-		} => handle_paclet_new(name, shorten_to_base_name),
-		PacletCommand::Build {
-			paclet_dir,
-			build_dir,
-			install,
-		} => handle_paclet_build(paclet_dir, build_dir, install),
+			template,
+		} => handle_paclet_new(name, shorten_to_base_name, template),
+		PacletCommand::Build(args) => handle_paclet_build(args),
 		PacletCommand::Doc {
 			paclet_dir,
 			build_dir,
 			html,
 			open,
-		} => handle_paclet_doc(paclet_dir, build_dir, html, open),
-		PacletCommand::Install { paclet_file } => {
-			handle_paclet_install(paclet_file)
+			workspace,
+		} => handle_paclet_doc(paclet_dir, build_dir, html, open, workspace),
+		PacletCommand::Install {
+			paclet,
+			force_version: _,
+			no_force_version,
+			dry_run,
+			json,
+		} => handle_paclet_install(paclet, !no_force_version, dry_run, json),
+		PacletCommand::List { pattern, json } => {
+			handle_paclet_list(pattern, json)
 		},
-		PacletCommand::Test {
-			paclet_dir,
-			tests_path,
-			diff_context,
-		} => handle_paclet_test(paclet_dir, tests_path, diff_context),
+		PacletCommand::Uninstall { paclet, yes } => {
+			handle_paclet_uninstall(paclet, yes)
+		},
+		PacletCommand::Test(args) => handle_paclet_test(args),
+		PacletCommand::Info { paclet, json } => {
+			handle_paclet_info(paclet, json)
+		},
+		PacletCommand::Bench(args) => handle_paclet_bench(args),
 	}
 }
 
@@ -318,97 +749,49 @@ fn process_until_ready_for_input(
 // $ wolfram paclet new
 //======================================
 
-fn handle_paclet_new(name: String, shorten_to_base_name: bool) {
+fn handle_paclet_new(
+	name: PacletName,
+	shorten_to_base_name: bool,
+	template: String,
+) {
 	let paclet_parent_dir = std::env::current_dir()
 		.expect("unable to get current working directory");
 
-	// if verbosity > 0 {
-	// 	eprintln!(
-	// 		"creating paclet with name: {name} at {}",
-	// 		paclet_root.display()
-	// 	)
-	// }
-
-	//------------------------------------------------------
-	// Launch the WolframKernel to evaluate CreatePaclet[..]
-	//------------------------------------------------------
-
-	let mut kernel = kernel::launch_kernel();
-
-	match kernel.packets().next() {
-		Some(Packet::InputName(_)) => (),
-		other => panic!("unexpected WolframKernel first packet: {other:?}"),
+	let template = match template::Template::from_name(&template) {
+		Ok(template) => template,
+		Err(err) => {
+			eprintln!("{} {err}", "error:".red().bold());
+			std::process::exit(1);
+		},
 	};
 
-	// Evaluate:
-	//
-	//     Needs["PacletTools`"]
-	kernel
-		.enter_and_wait_with_output_handler(
-			Expr::normal(
-				Symbol::new("System`Needs"),
-				vec![Expr::string("PacletTools`")],
-			),
-			&mut print_command_output,
-		)
-		.unwrap_null();
+	let (variables, dir_name) = match name {
+		PacletName::Resource { publisher, base } => {
+			let dir_name = match shorten_to_base_name {
+				true => base.clone(),
+				// Use a double underscore instead of a '/' in the paclet root
+				// directory name.
+				false => format!("{publisher}__{base}"),
+			};
 
-	// Evaluate:
-	//
-	//     CreatePaclet[name, paclet_root]
-	kernel
-		.enter_and_wait_with_output_handler(
-			Expr::normal(
-				Symbol::new("PacletTools`CreatePaclet"),
-				vec![
-					Expr::string(&name),
-					Expr::string(
-						paclet_parent_dir.to_str().expect(
-							"paclet parent directory is not valid UTF-8",
-						),
-					),
-				],
-			),
-			&mut print_command_output,
-		)
-		.unwrap_returned();
+			(template::Variables::new(Some(&publisher), &base), dir_name)
+		},
+		PacletName::Normal(name) => {
+			(template::Variables::new(None, &name), name)
+		},
+	};
 
-	// Evaluate:
-	//
-	//     Exit[]
-	//
-	// Wait for the kernel to execute the commands we sent and shutdown
-	// gracefully.
-	let outcome = kernel.enter_and_wait_with_output_handler(
-		Expr::normal(Symbol::new("System`Exit"), vec![]),
-		&mut print_command_output,
-	);
+	let paclet_dir = paclet_parent_dir.join(dir_name);
 
-	if outcome != EvaluationOutcome::KernelQuit {
-		panic!("WolframKernel did not shutdown as expected: {outcome:?}");
+	if let Err(err) = template.instantiate(&paclet_dir, &variables) {
+		eprintln!("{} error creating paclet: {err}", "error:".red().bold());
+		std::process::exit(1);
 	}
 
-	// TODO(cleanup): Change CreatePaclet to support an option for creating the
-	//                new paclet with the base name directly, so we don't have
-	//                to do this rename after it has been created.
-	if let PacletName::Resource { publisher, base } =
-		PacletName::from_str(&name).expect("malformed paclet name")
-	{
-		if shorten_to_base_name {
-			// Use a double underscore instead of a '/' in the paclet root
-			// directory name.
-			let current = format!("{publisher}__{base}");
-			let desired = base;
-
-			std::fs::rename(
-				paclet_parent_dir.join(&current),
-				paclet_parent_dir.join(&desired),
-			)
-			.expect("error shortening paclet name")
-		}
-	};
+	println!("Created paclet at: {}", paclet_dir.display());
 }
 
+#[derive(Debug, Clone)]
 enum PacletName {
 	Resource { publisher: String, base: String },
 	Normal(String),
@@ -421,111 +804,179 @@ impl FromStr for PacletName {
 		let components: Vec<&str> = name.split('/').collect();
 
 		match *components {
-			[_] => Ok(PacletName::Normal(name.to_owned())),
-			[publisher, base] => Ok(PacletName::Resource {
-				publisher: publisher.to_owned(),
-				base: base.to_owned(),
-			}),
+			[base] => {
+				validate_paclet_name_component("name", base)?;
+
+				Ok(PacletName::Normal(base.to_owned()))
+			},
+			[publisher, base] => {
+				validate_paclet_name_component("publisher", publisher)?;
+				validate_paclet_name_component("base name", base)?;
+
+				Ok(PacletName::Resource {
+					publisher: publisher.to_owned(),
+					base: base.to_owned(),
+				})
+			},
 			[..] => Err(format!(
-				"paclet names can contain at most one forward slash ('/') character: {:?}",
-				name
+				"expected a name of the form 'Publisher/BaseName' or 'BaseName', but {name:?} contains {} '/' characters",
+				components.len() - 1
 			)),
 		}
 	}
 }
 
+/// Check that `component` is a valid paclet publisher or base name.
+///
+/// The publisher and base name form the primary context of the paclet, so
+/// they must also be valid context names.
+fn validate_paclet_name_component(
+	kind: &str,
+	component: &str,
+) -> Result<(), String> {
+	let Some(first) = component.chars().next() else {
+		return Err(format!("paclet {kind} is empty"));
+	};
+
+	if !first.is_ascii_alphabetic() {
+		return Err(format!(
+			"paclet {kind} {component:?} must start with a letter, not {first:?}"
+		));
+	}
+
+	if let Some(invalid) =
+		component.chars().find(|char| !char.is_ascii_alphanumeric())
+	{
+		return Err(format!(
+			"paclet {kind} {component:?} contains {invalid:?}, but may only contain letters and digits"
+		));
+	}
+
+	Ok(())
+}
+
 //======================================
 // $ wolfram paclet install
 //======================================
 
-fn handle_paclet_install(paclet_file: PathBuf) {
-	let paclet_file = match paclet_file.canonicalize() {
-		Ok(file) => file,
-		// TODO: Make this error nicer, as this can commonly occur if the
-		//       specified file path doesn't exist.
-		Err(err) => todo!("error getting absolute paclet file path: {err}"),
-	};
+fn handle_paclet_install(
+	paclet: String,
+	force_version: bool,
+	dry_run: bool,
+	json: bool,
+) {
+	let path = Path::new(&paclet);
 
-	let paclet_file: &str = match paclet_file.to_str() {
-		Some(paclet_file) => paclet_file,
-		None => panic!(".paclet file path is not valid UTF-8"),
-	};
+	// Treat the argument as a paclet name unless it names an existing file or
+	// directory, or looks like a `.paclet` file or directory path.
+	let source = if path.exists()
+		|| path.extension().is_some_and(|ext| ext == "paclet")
+	{
+		paths::require_exists(path, ".paclet file", PathKind::PacletFile);
 
-	let mut kernel = kernel::launch_kernel();
+		let path = match path.canonicalize() {
+			Ok(path) => path,
+			Err(err) => panic!(
+				"error getting absolute paclet file path: {}: {err}",
+				path.display()
+			),
+		};
 
-	match kernel.packets().next() {
-		Some(Packet::InputName(_)) => (),
-		other => panic!("unexpected WolframKernel first packet: {other:?}"),
+		path_to_expr(&path)
+	} else if looks_like_path(&paclet) {
+		paths::require_exists(
+			path,
+			".paclet file or paclet directory",
+			PathKind::Any,
+		);
+
+		unreachable!("path does not exist, so require_exists() exited")
+	} else {
+		let spec = match PacletSpec::from_str(&paclet) {
+			Ok(spec) => spec,
+			Err(err) => {
+				eprintln!("{} {err}", "error:".red().bold());
+				std::process::exit(1);
+			},
+		};
+
+		let version = match spec.version {
+			Some(version) => Expr::string(version),
+			None => Expr::symbol(Symbol::new("System`All")),
+		};
+
+		Expr::list(vec![Expr::string(spec.name), version])
 	};
 
-	load_wolfram_cli_paclet(&mut kernel);
+	let mut kernel = launch_wolfram_cli_kernel();
 
 	// Evaluate:
 	//
-	//     CommandPacletInstall[paclet_file]
+	//     CommandPacletInstall[
+	//         source,
+	//         "ForceVersionInstall" -> force_version,
+	//         "DryRun" -> dry_run,
+	//         "JSON" -> json
+	//     ]
 	let outcome = kernel.enter_and_wait_with_output_handler(
 		Expr::normal(
 			Symbol::new("ConnorGray`WolframCLI`CommandPacletInstall"),
-			vec![Expr::string(paclet_file)],
+			vec![
+				source,
+				Expr::rule(
+					Expr::string("ForceVersionInstall"),
+					Expr::from(force_version),
+				),
+				Expr::rule(Expr::string("DryRun"), Expr::from(dry_run)),
+				Expr::rule(Expr::string("JSON"), Expr::from(json)),
+			],
 		),
 		&mut print_command_output,
 	);
 
 	match outcome {
 		EvaluationOutcome::Null => (),
-		EvaluationOutcome::Returned(returned) => {
-			todo!("unexpected return value: {returned:?}")
-		},
+		// The paclet could not be installed. CommandPacletInstall has already
+		// printed the error.
+		EvaluationOutcome::Returned(_) => std::process::exit(1),
 		EvaluationOutcome::KernelQuit => {
 			todo!("Kernel unexpectedly quit")
 		},
 	};
 }
 
+/// Whether `arg` looks like a file path rather than a paclet name.
+///
+/// Paclet names can contain a single `/` separating the publisher from the
+/// rest of the name, e.g. `Wolfram/ErrorTools`, so only arguments that are
+/// absolute, start with `.`, or contain more than one separator are treated
+/// as paths.
+fn looks_like_path(arg: &str) -> bool {
+	Path::new(arg).is_absolute()
+		|| arg.starts_with('.')
+		|| arg.contains('\\')
+		|| arg.matches('/').count() > 1
+}
+
 //======================================
-// $ wolfram paclet build [PACLET_DIR] [BUILD_DIR]
+// $ wolfram paclet list [PATTERN] [--json]
 //======================================
 
-fn handle_paclet_build(
-	paclet_dir: Option<PathBuf>,
-	build_dir: Option<PathBuf>,
-	install: bool,
-) {
-	let paclet_dir = unwrap_path_or_default_to_current_dir(paclet_dir);
-	let paclet_dir: &str = match paclet_dir.to_str() {
-		Some(paclet_dir) => paclet_dir,
-		None => panic!("paclet directory path is not valid UTF-8"),
-	};
-
-	let mut kernel = kernel::launch_kernel();
-
-	match kernel.packets().next() {
-		Some(Packet::InputName(_)) => (),
-		other => panic!("unexpected WolframKernel first packet: {other:?}"),
+fn handle_paclet_list(pattern: Option<String>, json: bool) {
+	let pattern = match pattern {
+		Some(pattern) => Expr::string(pattern),
+		None => Expr::symbol(Symbol::new("System`All")),
 	};
 
-	load_wolfram_cli_paclet(&mut kernel);
-
-	let build_dir: Expr = match build_dir {
-		Some(build_dir) => {
-			let build_dir: &str = match build_dir.to_str() {
-				Some(build_dir) => build_dir,
-				None => {
-					panic!("paclet build directory path is not valid UTF-8")
-				},
-			};
-			Expr::string(build_dir)
-		},
-		None => Expr::symbol(Symbol::new("System`Automatic")),
-	};
+	let mut kernel = launch_wolfram_cli_kernel();
 
 	// Evaluate:
 	//
-	//     CommandPacletBuild[paclet_dir, build_dir]
+	//     CommandPacletList[pattern, "JSON" -> json]
 	let outcome = kernel.enter_and_wait_with_output_handler(
 		Expr::normal(
-			Symbol::new("ConnorGray`WolframCLI`CommandPacletBuild"),
-			vec![Expr::string(paclet_dir), build_dir, Expr::from(install)],
+			Symbol::new("ConnorGray`WolframCLI`CommandPacletList"),
+			vec![pattern, Expr::rule(Expr::string("JSON"), Expr::from(json))],
 		),
 		&mut print_command_output,
 	);
@@ -533,58 +984,651 @@ fn handle_paclet_build(
 	match outcome {
 		EvaluationOutcome::Null => (),
 		EvaluationOutcome::Returned(returned) => {
-			todo!("unexpected return value: {returned:?}")
+			let returned = match returned {
+				PacketExpr::Expr(expr) => expr.to_string(),
+				PacketExpr::Text(text) => text,
+			};
+
+			eprintln!(
+				"{} unable to list installed paclets: {returned}",
+				"error:".red().bold()
+			);
+			std::process::exit(1);
 		},
 		EvaluationOutcome::KernelQuit => {
-			todo!("Kernel unexpectedly quit")
+			eprintln!(
+				"{} Wolfram Kernel quit unexpectedly",
+				"error:".red().bold()
+			);
+			std::process::exit(1);
 		},
 	};
 }
 
 //======================================
-// $ wolfram paclet doc [PACLET_DIR] [--html] [--open]
+// $ wolfram paclet uninstall <NAME[@VERSION]> [--yes]
 //======================================
 
-fn handle_paclet_doc(
-	paclet_dir: Option<PathBuf>,
-	build_dir: Option<PathBuf>,
-	html: bool,
-	open: bool,
-) {
-	let paclet_dir = unwrap_path_or_default_to_current_dir(paclet_dir);
-	let paclet_dir: &str = match paclet_dir.to_str() {
-		Some(paclet_dir) => paclet_dir,
-		None => panic!("paclet directory path is not valid UTF-8"),
-	};
+fn handle_paclet_uninstall(paclet: PacletSpec, yes: bool) {
+	let mut kernel = launch_wolfram_cli_kernel();
 
-	let build_dir: Expr = match build_dir {
-		Some(build_dir) => {
-			let build_dir: &str = match build_dir.to_str() {
-				Some(build_dir) => build_dir,
-				None => {
-					panic!("paclet build directory path is not valid UTF-8")
-				},
-			};
-			Expr::string(build_dir)
-		},
-		None => Expr::symbol(Symbol::new("System`Automatic")),
-	};
+	if !yes {
+		// Print the paclets that would be uninstalled, without uninstalling
+		// them.
+		let counts = evaluate_paclet_uninstall(&mut kernel, &paclet, false);
 
-	let mut kernel = kernel::launch_kernel();
+		if counts.map(|(paclet_count, _)| paclet_count).unwrap_or(0) == 0 {
+			std::process::exit(1);
+		}
 
-	match kernel.packets().next() {
-		Some(Packet::InputName(_)) => (),
-		other => panic!("unexpected WolframKernel first packet: {other:?}"),
-	};
+		if !confirm("Uninstall these paclets?") {
+			println!("Nothing was uninstalled.");
+			return;
+		}
+	}
 
-	load_wolfram_cli_paclet(&mut kernel);
+	let counts = evaluate_paclet_uninstall(&mut kernel, &paclet, true);
+
+	if !matches!(counts, Some((paclet_count, 0)) if paclet_count > 0) {
+		std::process::exit(1);
+	}
+}
+
+/// Evaluate `CommandPacletUninstall`, returning the number of paclets that
+/// matched `paclet` and the number that could not be uninstalled.
+fn evaluate_paclet_uninstall(
+	kernel: &mut WolframSession,
+	paclet: &PacletSpec,
+	confirmed: bool,
+) -> Option<(usize, usize)> {
+	let version = match &paclet.version {
+		Some(version) => Expr::string(version),
+		None => Expr::symbol(Symbol::new("System`All")),
+	};
 
 	// Evaluate:
 	//
-	//     CommandPacletDoc[paclet_dir, build_dir, html, open]
+	//     CommandPacletUninstall[name, version, "Confirmed" -> confirmed]
 	let outcome = kernel.enter_and_wait_with_output_handler(
 		Expr::normal(
-			Symbol::new("ConnorGray`WolframCLI`CommandPacletDoc"),
+			Symbol::new("ConnorGray`WolframCLI`CommandPacletUninstall"),
+			vec![
+				Expr::string(&paclet.name),
+				version,
+				Expr::rule(Expr::string("Confirmed"), Expr::from(confirmed)),
+			],
+		),
+		&mut print_command_output,
+	);
+
+	match outcome {
+		EvaluationOutcome::Null => None,
+		EvaluationOutcome::Returned(returned) => match returned {
+			PacketExpr::Expr(expr) => Some((
+				lookup_count(&expr, "PacletCount")?,
+				lookup_count(&expr, "FailureCount")?,
+			)),
+			PacketExpr::Text(_) => None,
+		},
+		EvaluationOutcome::KernelQuit => {
+			eprintln!(
+				"{} Wolfram Kernel quit unexpectedly",
+				"error:".red().bold()
+			);
+			std::process::exit(1);
+		},
+	}
+}
+
+/// Name of a paclet, optionally with a specific version, written
+/// as `NAME` or `NAME@VERSION`.
+#[derive(Debug, Clone)]
+struct PacletSpec {
+	name: String,
+	version: Option<String>,
+}
+
+impl FromStr for PacletSpec {
+	type Err = String;
+
+	fn from_str(spec: &str) -> Result<Self, Self::Err> {
+		let (name, version) = match spec.split_once('@') {
+			Some((name, version)) => (name, Some(version)),
+			None => (spec, None),
+		};
+
+		if name.is_empty() {
+			return Err(format!("paclet name in {spec:?} is empty"));
+		}
+
+		if version.is_some_and(|version| version.is_empty()) {
+			return Err(format!("paclet version in {spec:?} is empty"));
+		}
+
+		Ok(PacletSpec {
+			name: name.to_owned(),
+			version: version.map(str::to_owned),
+		})
+	}
+}
+
+//======================================
+// $ wolfram paclet build [PACLET_DIR] [BUILD_DIR]
+//======================================
+
+fn handle_paclet_build(args: PacletBuildArgs) {
+	if args.workspace {
+		return build_workspace(&args);
+	}
+
+	if let Some(paclet_dir) = &args.paclet_dir {
+		paths::require_exists(
+			paclet_dir,
+			"paclet directory",
+			PathKind::Directory,
+		);
+	}
+
+	let paclet_dir =
+		unwrap_path_or_default_to_current_dir(args.paclet_dir.clone());
+
+	if args.watch {
+		let mut kernel = launch_wolfram_cli_kernel();
+
+		let command = paclet_build_command(
+			&paclet_dir,
+			args.build_dir.as_deref(),
+			args.install,
+			args.isolated,
+			true,
+		);
+
+		return watch_paclet_build(
+			&mut kernel,
+			&paclet_dir,
+			args.build_dir.as_deref(),
+			&args.cargo_profile,
+			command,
+		);
+	}
+
+	let archive =
+		build_paclet(&mut None, launch_wolfram_cli_kernel, &paclet_dir, &args);
+
+	// The build failed, and CommandPacletBuild has already reported why.
+	if archive.is_none() {
+		std::process::exit(1);
+	}
+}
+
+/// Build each member of the workspace containing the current directory, in
+/// dependency order.
+fn build_workspace(args: &PacletBuildArgs) {
+	let workspace = load_workspace();
+	let dirs = workspace.member_dirs();
+
+	let mut kernel = None;
+	let mut results = Vec::new();
+
+	for member in &workspace.members {
+		print_workspace_member_header("Building", member);
+
+		let archive = build_paclet(
+			&mut kernel,
+			|| launch_workspace_kernel(&dirs),
+			&member.dir,
+			args,
+		);
+
+		results.push(match archive {
+			Some(archive) => Ok(archive.display().to_string()),
+			None => Err("build failed".to_owned()),
+		});
+	}
+
+	print_workspace_summary(&workspace, &results);
+}
+
+/// Build the paclet in `paclet_dir` using the build options in `args`,
+/// returning the path of the built `.paclet` archive, or `None` if the build
+/// failed.
+///
+/// If the paclet is unchanged since the last successful build, the archive
+/// from that build is used instead, unless `--force` was specified. `kernel`
+/// is launched using `launch` the first time it is needed.
+///
+/// Any Rust LibraryLink crate declared by the paclet is built first.
+fn build_paclet(
+	kernel: &mut Option<WolframSession>,
+	launch: impl Fn() -> WolframSession,
+	paclet_dir: &Path,
+	args: &PacletBuildArgs,
+) -> Option<PathBuf> {
+	let PacletBuildArgs {
+		ref build_dir,
+		install,
+		isolated,
+		force,
+		ref cargo_profile,
+		..
+	} = *args;
+	let build_dir = build_dir.as_deref();
+
+	// The library built by the crate is an input of the paclet build, so it
+	// must be built before checking whether the paclet is unchanged.
+	if !build_paclet_library_crate(paclet_dir, cargo_profile, || {
+		kernel_system_id(kernel.get_or_insert_with(&launch))
+	}) {
+		return None;
+	}
+
+	let cache = {
+		let paclet_dir = paclet_dir
+			.canonicalize()
+			.expect("unable to get absolute paclet directory path");
+		let ignored = paclet_non_source_dirs(&paclet_dir, build_dir);
+
+		match build_cache::BuildCache::new(
+			&paclet_dir,
+			// The first non-source directory is the build directory.
+			&ignored[0],
+			&ignored,
+			&[
+				("Isolated", isolated.to_string()),
+				("CargoProfile", cargo_profile.clone()),
+			],
+		) {
+			Ok(cache) => Some(cache),
+			Err(err) => {
+				eprintln!("warning: unable to use paclet build cache: {err}");
+				None
+			},
+		}
+	};
+
+	if let Some(archive) = cache
+		.as_ref()
+		.filter(|_| !force)
+		.and_then(|cache| cache.cached_archive())
+	{
+		println!(
+			"{} {}",
+			"Paclet is unchanged since the last build.".green(),
+			"(use --force to rebuild it)".dimmed()
+		);
+		println!("Paclet Archive: {}", archive.display());
+
+		if install {
+			let kernel = kernel.get_or_insert_with(&launch);
+
+			if !install_paclet_archive(kernel, &archive) {
+				return None;
+			}
+		}
+
+		return Some(archive);
+	}
+
+	let kernel = kernel.get_or_insert_with(&launch);
+
+	let outcome = kernel.enter_and_wait_with_output_handler(
+		paclet_build_command(paclet_dir, build_dir, install, isolated, false),
+		&mut print_command_output,
+	);
+
+	let archive = match outcome {
+		EvaluationOutcome::Null => None,
+		EvaluationOutcome::Returned(returned) => match returned {
+			PacketExpr::Expr(expr) => lookup(&expr, "PacletArchive")
+				.and_then(Expr::try_as_str)
+				.map(PathBuf::from),
+			PacketExpr::Text(_) => None,
+		},
+		EvaluationOutcome::KernelQuit => {
+			todo!("Kernel unexpectedly quit")
+		},
+	}?;
+
+	if let Some(cache) = cache {
+		if let Err(err) = cache.save(&archive) {
+			eprintln!("warning: unable to save paclet build cache: {err}");
+		}
+	}
+
+	Some(archive)
+}
+
+/// Get the Rust LibraryLink crate declared by the paclet in `paclet_dir`, if
+/// any.
+fn paclet_library_crate(
+	paclet_dir: &Path,
+) -> Result<Option<cargo::LibraryCrate>, String> {
+	// Paclets whose metadata can't be read natively can't declare a crate, and
+	// any problem with their metadata will be reported by PacletBuild.
+	let Ok(info) = paclet_info::PacletInfo::read(paclet_dir) else {
+		return Ok(None);
+	};
+
+	cargo::LibraryCrate::find(paclet_dir, &info)
+}
+
+/// Build the Rust LibraryLink crate declared by the paclet in `paclet_dir`, if
+/// any, returning `false` if it could not be built.
+///
+/// `system_id` is called to get the `$SystemID` of the Kernel the library will
+/// be loaded by, only if the paclet declares a crate.
+fn build_paclet_library_crate(
+	paclet_dir: &Path,
+	cargo_profile: &str,
+	system_id: impl FnOnce() -> String,
+) -> bool {
+	let result = paclet_library_crate(paclet_dir).and_then(|library_crate| {
+		match library_crate {
+			Some(library_crate) => {
+				library_crate.build(cargo_profile, &system_id())
+			},
+			None => Ok(()),
+		}
+	});
+
+	match result {
+		Ok(()) => true,
+		Err(err) => {
+			eprintln!("{} {err}", "error:".red().bold());
+			false
+		},
+	}
+}
+
+/// Get the `$SystemID` of `kernel`.
+fn kernel_system_id(kernel: &mut WolframSession) -> String {
+	// Evaluate:
+	//
+	//     $SystemID
+	let returned = kernel
+		.enter_and_wait_with_output_handler(
+			Expr::symbol(Symbol::new("System`$SystemID")),
+			&mut print_command_output,
+		)
+		.unwrap_returned();
+
+	match returned {
+		PacketExpr::Expr(expr) => match expr.try_as_str() {
+			Some(system_id) => system_id.to_owned(),
+			None => panic!("$SystemID is not a string: {expr}"),
+		},
+		PacketExpr::Text(text) => text.trim_matches('"').to_owned(),
+	}
+}
+
+/// Get the `CommandPacletBuild[..]` expression that builds the paclet in
+/// `paclet_dir`.
+fn paclet_build_command(
+	paclet_dir: &Path,
+	build_dir: Option<&Path>,
+	install: bool,
+	isolated: bool,
+	compact: bool,
+) -> Expr {
+	let paclet_dir: &str = match paclet_dir.to_str() {
+		Some(paclet_dir) => paclet_dir,
+		None => panic!("paclet directory path is not valid UTF-8"),
+	};
+
+	let build_dir: Expr = match build_dir {
+		Some(build_dir) => {
+			let build_dir: &str = match build_dir.to_str() {
+				Some(build_dir) => build_dir,
+				None => {
+					panic!("paclet build directory path is not valid UTF-8")
+				},
+			};
+			Expr::string(build_dir)
+		},
+		None => Expr::symbol(Symbol::new("System`Automatic")),
+	};
+
+	// Construct:
+	//
+	//     CommandPacletBuild[
+	//         paclet_dir, build_dir, install,
+	//         "Isolated" -> isolated,
+	//         "Compact" -> compact
+	//     ]
+	Expr::normal(
+		Symbol::new("ConnorGray`WolframCLI`CommandPacletBuild"),
+		vec![
+			Expr::string(paclet_dir),
+			build_dir,
+			Expr::from(install),
+			Expr::rule(Expr::string("Isolated"), Expr::from(isolated)),
+			Expr::rule(Expr::string("Compact"), Expr::from(compact)),
+		],
+	)
+}
+
+/// Install the `.paclet` file `archive`, returning `false` if it could not be
+/// installed.
+fn install_paclet_archive(kernel: &mut WolframSession, archive: &Path) -> bool {
+	// Evaluate:
+	//
+	//     CommandPacletInstall[archive]
+	let outcome = kernel.enter_and_wait_with_output_handler(
+		Expr::normal(
+			Symbol::new("ConnorGray`WolframCLI`CommandPacletInstall"),
+			vec![path_to_expr(archive)],
+		),
+		&mut print_command_output,
+	);
+
+	match outcome {
+		EvaluationOutcome::Null => true,
+		// CommandPacletInstall has already printed the error.
+		EvaluationOutcome::Returned(_) => false,
+		EvaluationOutcome::KernelQuit => {
+			eprintln!(
+				"{} Wolfram Kernel quit unexpectedly",
+				"error:".red().bold()
+			);
+			std::process::exit(1);
+		},
+	}
+}
+
+/// Get the directories in or used by `paclet_dir` whose contents are not
+/// inputs to a paclet build, starting with the build directory.
+///
+/// `paclet_dir` must be an absolute path.
+fn paclet_non_source_dirs(
+	paclet_dir: &Path,
+	build_dir: Option<&Path>,
+) -> Vec<PathBuf> {
+	let build_dir = match build_dir {
+		// The build directory must be canonical to be compared against the
+		// paths of paclet files, and may not have been created yet.
+		Some(build_dir) => paths::canonicalize_lenient(build_dir),
+		None => paclet_dir.join("build"),
+	};
+
+	vec![
+		build_dir,
+		paclet_dir.join("build"),
+		paclet_dir.join("target"),
+		paclet_dir.join(".git"),
+	]
+}
+
+/// Rebuild the paclet in `paclet_dir` by evaluating `command` each time the
+/// files in `paclet_dir` change.
+///
+/// If the paclet declares a Rust LibraryLink crate, the crate is rebuilt before
+/// each paclet build, and changes to the files of the crate also trigger a
+/// rebuild.
+fn watch_paclet_build(
+	kernel: &mut WolframSession,
+	paclet_dir: &Path,
+	build_dir: Option<&Path>,
+	cargo_profile: &str,
+	command: Expr,
+) {
+	// Absolute paths are needed to compare against the paths reported by the
+	// file system watcher.
+	let paclet_dir = paclet_dir
+		.canonicalize()
+		.expect("unable to get absolute paclet directory path");
+
+	// Don't rebuild when build artifacts are written into the paclet
+	// directory, or when files that can't be part of the paclet change.
+	let mut ignored = paclet_non_source_dirs(&paclet_dir, build_dir);
+
+	let mut watched = vec![paclet_dir.clone()];
+
+	if let Ok(Some(library_crate)) = paclet_library_crate(&paclet_dir) {
+		if let Ok(crate_dir) = library_crate.dir().canonicalize() {
+			if !crate_dir.starts_with(&paclet_dir) {
+				ignored.push(crate_dir.join("target"));
+				watched.push(crate_dir);
+			}
+		}
+	}
+
+	let watched: Vec<&Path> = watched.iter().map(PathBuf::as_path).collect();
+
+	let watcher = watch::Watcher::new(&watched, ignored);
+
+	let mut build = || {
+		// A failed crate build has already been reported, and the paclet
+		// shouldn't be built with an out of date library.
+		if !build_paclet_library_crate(&paclet_dir, cargo_profile, || {
+			kernel_system_id(kernel)
+		}) {
+			return;
+		}
+
+		let outcome = kernel.enter_and_wait_with_output_handler(
+			command.clone(),
+			&mut print_command_output,
+		);
+
+		// Failed builds have already been reported by CommandPacletBuild, and
+		// shouldn't stop the paclet from being rebuilt after the next change.
+		if let EvaluationOutcome::KernelQuit = outcome {
+			eprintln!(
+				"{} Wolfram Kernel quit unexpectedly",
+				"error:".red().bold()
+			);
+			std::process::exit(1);
+		}
+	};
+
+	build();
+
+	println!(
+		"{}",
+		"Watching for changes... (press Ctrl-C to exit)".dimmed()
+	);
+
+	loop {
+		watcher.wait_for_changes();
+
+		build();
+	}
+}
+
+//======================================
+// $ wolfram paclet doc [PACLET_DIR] [--html] [--open]
+//======================================
+
+fn handle_paclet_doc(
+	paclet_dir: Option<PathBuf>,
+	build_dir: Option<PathBuf>,
+	html: bool,
+	open: bool,
+	workspace: bool,
+) {
+	if workspace {
+		return doc_workspace();
+	}
+
+	if let Some(paclet_dir) = &paclet_dir {
+		paths::require_exists(
+			paclet_dir,
+			"paclet directory",
+			PathKind::Directory,
+		);
+	}
+
+	let paclet_dir = unwrap_path_or_default_to_current_dir(paclet_dir);
+
+	let mut kernel = launch_wolfram_cli_kernel();
+
+	if !build_paclet_docs(
+		&mut kernel,
+		&paclet_dir,
+		build_dir.as_deref(),
+		html,
+		open,
+	) {
+		std::process::exit(1);
+	}
+}
+
+/// Build the documentation of each member of the workspace containing the
+/// current directory, in dependency order.
+fn doc_workspace() {
+	let workspace = load_workspace();
+
+	let mut kernel = launch_workspace_kernel(&workspace.member_dirs());
+	let mut results = Vec::new();
+
+	for member in &workspace.members {
+		print_workspace_member_header("Building documentation for", member);
+
+		let succeeded =
+			build_paclet_docs(&mut kernel, &member.dir, None, false, false);
+
+		results.push(match succeeded {
+			true => Ok("documentation built".to_owned()),
+			false => Err("documentation build failed".to_owned()),
+		});
+	}
+
+	print_workspace_summary(&workspace, &results);
+}
+
+/// Build the documentation of the paclet in `paclet_dir`, returning `false` if
+/// the build failed.
+fn build_paclet_docs(
+	kernel: &mut WolframSession,
+	paclet_dir: &Path,
+	build_dir: Option<&Path>,
+	html: bool,
+	open: bool,
+) -> bool {
+	let paclet_dir: &str = match paclet_dir.to_str() {
+		Some(paclet_dir) => paclet_dir,
+		None => panic!("paclet directory path is not valid UTF-8"),
+	};
+
+	let build_dir: Expr = match build_dir {
+		Some(build_dir) => {
+			let build_dir: &str = match build_dir.to_str() {
+				Some(build_dir) => build_dir,
+				None => {
+					panic!("paclet build directory path is not valid UTF-8")
+				},
+			};
+			Expr::string(build_dir)
+		},
+		None => Expr::symbol(Symbol::new("System`Automatic")),
+	};
+
+	// Evaluate:
+	//
+	//     CommandPacletDoc[paclet_dir, build_dir, html, open]
+	let outcome = kernel.enter_and_wait_with_output_handler(
+		Expr::normal(
+			Symbol::new("ConnorGray`WolframCLI`CommandPacletDoc"),
 			vec![
 				Expr::string(paclet_dir),
 				build_dir,
@@ -592,92 +1636,852 @@ fn handle_paclet_doc(
 				Expr::from(open),
 			],
 		),
-		&mut print_command_output,
-	);
+		&mut print_command_output,
+	);
+
+	match outcome {
+		EvaluationOutcome::Null => true,
+		// CommandPacletDoc has already printed the error.
+		EvaluationOutcome::Returned(_) => false,
+		EvaluationOutcome::KernelQuit => {
+			todo!("Kernel unexpectedly quit")
+		},
+	}
+}
+
+//======================================
+// $ wolfram paclet test [PACLET_DIR]
+//======================================
+
+fn handle_paclet_test(args: PacletTestArgs) {
+	let PacletTestArgs {
+		paclet_dir,
+		tests_path,
+		run,
+		watch,
+		rerun,
+		coverage,
+		disable_installed,
+		isolated,
+		kernels,
+		all_kernels,
+		workspace,
+	} = args;
+
+	if let Some(paclet_dir) = &paclet_dir {
+		paths::require_exists(
+			paclet_dir,
+			"paclet directory",
+			PathKind::Directory,
+		);
+	}
+
+	if let Some(tests_path) = &tests_path {
+		paths::require_exists(tests_path, "tests path", PathKind::Any);
+	}
+
+	let paclet_dir = unwrap_path_or_default_to_current_dir(paclet_dir);
+
+	//
+	// Options
+	//
+
+	let (mut options, diff) = run.into_options();
+
+	if coverage {
+		options.push(Expr::rule(Expr::string("Coverage"), Expr::from(true)));
+	}
+
+	if disable_installed {
+		options.push(Expr::rule(
+			Expr::string("DisableInstalled"),
+			Expr::from(true),
+		));
+	}
+
+	if isolated {
+		options.push(Expr::rule(Expr::string("Isolated"), Expr::from(true)));
+	}
+
+	if workspace {
+		return test_workspace(options, diff);
+	}
+
+	if watch {
+		return watch_paclet_tests(
+			&paclet_dir,
+			tests_path,
+			options,
+			rerun,
+			diff,
+		);
+	}
+
+	let tests_path = tests_path.map(|path| path_to_expr(&path));
+
+	if all_kernels || !kernels.is_empty() {
+		return test_paclet_with_kernels(
+			&paclet_dir,
+			tests_path,
+			options,
+			&kernels,
+			diff,
+		);
+	}
+
+	let mut kernel = launch_wolfram_cli_kernel();
+
+	let summary =
+		run_paclet_tests(&mut kernel, &paclet_dir, tests_path, options, diff);
+
+	exit_if_tests_failed(&summary);
+}
+
+impl TestRunArgs {
+	/// Get the `CommandPacletTest` options corresponding to these arguments,
+	/// and the options for rendering diffs of failed tests.
+	fn into_options(self) -> (Vec<Expr>, DiffOptions) {
+		let TestRunArgs {
+			diff_context,
+			diff_style,
+			failed,
+			failed_first,
+			durations,
+			update_snapshots,
+			shuffle,
+			seed,
+		} = self;
+
+		let mut options = Vec::new();
+
+		// Diffs are rendered by print_test_output(), so request "TestDiff"
+		// events instead of textual diffs.
+		options.push(Expr::rule(
+			Expr::string("DiffStyle"),
+			match diff_style {
+				DiffStyle::Unified | DiffStyle::Split => Expr::string("Event"),
+				DiffStyle::None => Expr::symbol(Symbol::new("System`None")),
+			},
+		));
+
+		let diff = DiffOptions {
+			style: diff_style,
+			context: diff_context,
+		};
+
+		// The progress of the test run is displayed by print_test_output().
+		options
+			.push(Expr::rule(Expr::string("ProgressEvents"), Expr::from(true)));
+
+		if let Some(durations) = durations {
+			options.push(Expr::rule(
+				Expr::string("Durations"),
+				Expr::from(
+					i64::try_from(durations)
+						.expect("durations count overflows i64"),
+				),
+			));
+		}
+
+		if update_snapshots {
+			options.push(Expr::rule(
+				Expr::string("UpdateSnapshots"),
+				Expr::from(true),
+			));
+		}
+
+		if failed {
+			options.push(Expr::rule(
+				Expr::string("RerunFailed"),
+				Expr::from(true),
+			));
+		}
+
+		if failed_first {
+			options.push(Expr::rule(
+				Expr::string("FailedFirst"),
+				Expr::from(true),
+			));
+		}
+
+		if shuffle {
+			options.push(Expr::rule(Expr::string("Shuffle"), Expr::from(true)));
+		}
+
+		if let Some(seed) = seed {
+			options.push(Expr::rule(
+				Expr::string("ShuffleSeed"),
+				Expr::from(i64::from(seed)),
+			));
+		}
+
+		(options, diff)
+	}
+}
+
+/// Run the tests for `paclet_dir` once with each discovered Wolfram
+/// installation that matches one of the `requested` versions (or every
+/// installation if no versions are specified), and print a summary of the
+/// results for each version.
+fn test_paclet_with_kernels(
+	paclet_dir: &Path,
+	tests_path: Option<Expr>,
+	options: Vec<Expr>,
+	requested: &[String],
+	diff: DiffOptions,
+) {
+	let spec = paclet_wolfram_version(paclet_dir);
+
+	let mut apps = Vec::new();
+
+	for app in kernel::discover_apps() {
+		let version = match app.wolfram_version() {
+			Ok(version) => version,
+			Err(err) => {
+				eprintln!(
+					"warning: skipping Wolfram installation at {}: unable to read its version: {err:?}",
+					app.installation_directory().display()
+				);
+				continue;
+			},
+		};
+
+		if !requested.is_empty()
+			&& !requested
+				.iter()
+				.any(|request| kernel::version_satisfies(request, &version))
+		{
+			continue;
+		}
+
+		if !kernel::version_satisfies(&spec, &version) {
+			println!(
+				"{}",
+				format!(
+					"info: Skipping Wolfram {version}: the paclet requires WolframVersion {spec:?}"
+				)
+				.dimmed()
+			);
+			continue;
+		}
+
+		apps.push((version, app));
+	}
+
+	for request in requested {
+		let found = apps
+			.iter()
+			.any(|(version, _)| kernel::version_satisfies(request, version));
+
+		if !found {
+			eprintln!(
+				"warning: no compatible Wolfram installation found for version {request:?}"
+			);
+		}
+	}
+
+	if apps.is_empty() {
+		eprintln!(
+			"{} no Wolfram installations found to run the tests with",
+			"error:".red().bold()
+		);
+		std::process::exit(1);
+	}
+
+	let mut results = Vec::new();
+
+	for (version, app) in apps {
+		println!(
+			"\n{}",
+			format!(
+				"Testing with Wolfram {version} ({})",
+				app.installation_directory().display()
+			)
+			.bold()
+		);
+
+		let mut kernel =
+			init_wolfram_cli_kernel(kernel::launch_kernel_from_app(&app));
+
+		let summary = run_paclet_tests(
+			&mut kernel,
+			paclet_dir,
+			tests_path.clone(),
+			options.clone(),
+			diff,
+		);
+
+		results.push((version, summary));
+	}
+
+	println!("\n{}", "Results by Wolfram version:".bold());
+
+	for (version, summary) in &results {
+		let version = version.to_string();
+
+		match *summary {
+			Some(TestSummary {
+				test_count,
+				failure_count,
+			}) => {
+				let passed = test_count - failure_count;
+				let status = if failure_count == 0 {
+					"ok".green()
+				} else {
+					"FAILED".red()
+				};
+
+				println!(
+					"  {version:<10} {passed:>5} passed {failure_count:>5} failed   {status}"
+				);
+			},
+			None => {
+				println!("  {version:<10} {}", "error: tests did not run".red())
+			},
+		}
+	}
+
+	for (_, summary) in &results {
+		exit_if_tests_failed(summary);
+	}
+}
+
+/// Run the tests of each member of the workspace containing the current
+/// directory, in dependency order.
+fn test_workspace(options: Vec<Expr>, diff: DiffOptions) {
+	let workspace = load_workspace();
+
+	let mut kernel = launch_workspace_kernel(&workspace.member_dirs());
+	let mut results = Vec::new();
+
+	for member in &workspace.members {
+		print_workspace_member_header("Testing", member);
+
+		// The tests are run in a subkernel, so also make the other members
+		// available there.
+		let mut options = options.clone();
+		options.push(Expr::rule(
+			Expr::string("LoadDirectories"),
+			Expr::list(
+				workspace
+					.members
+					.iter()
+					.filter(|other| other.dir != member.dir)
+					.map(|other| path_to_expr(&other.dir))
+					.collect(),
+			),
+		));
+
+		let summary =
+			run_paclet_tests(&mut kernel, &member.dir, None, options, diff);
+
+		results.push(match summary {
+			Some(TestSummary {
+				test_count,
+				failure_count: 0,
+			}) => Ok(format!("{test_count} passed")),
+			Some(TestSummary {
+				test_count,
+				failure_count,
+			}) => Err(format!(
+				"{} passed, {failure_count} failed",
+				test_count - failure_count
+			)),
+			None => Err("error: tests did not run".to_owned()),
+		});
+	}
+
+	print_workspace_summary(&workspace, &results);
+}
+
+/// Get the `"WolframVersion"` field of the paclet in `paclet_dir`.
+///
+/// Paclets that don't specify a `"WolframVersion"`, or whose metadata can't be
+/// read, are treated as compatible with any version.
+fn paclet_wolfram_version(paclet_dir: &Path) -> String {
+	let info = match paclet_info::PacletInfo::read(paclet_dir) {
+		Ok(info) => info,
+		Err(err) => {
+			eprintln!(
+				"warning: unable to read paclet \"WolframVersion\", testing with all installations: {err}"
+			);
+			return "*".to_owned();
+		},
+	};
+
+	info.wolfram_version.unwrap_or_else(|| "*".to_owned())
+}
+
+/// Run the tests for `paclet_dir` every time a paclet source file or test file
+/// changes.
+fn watch_paclet_tests(
+	paclet_dir: &Path,
+	tests_path: Option<PathBuf>,
+	mut options: Vec<Expr>,
+	rerun: WatchRerun,
+	diff: DiffOptions,
+) {
+	// Absolute paths are needed to compare against the paths reported by the
+	// file system watcher.
+	let paclet_dir = paclet_dir
+		.canonicalize()
+		.expect("unable to get absolute paclet directory path");
+	let tests_path = tests_path.map(|path| {
+		path.canonicalize()
+			.expect("unable to get absolute paclet tests path")
+	});
+
+	let mut watched: Vec<&Path> = vec![&paclet_dir];
+
+	if let Some(tests_path) = &tests_path {
+		if !tests_path.starts_with(&paclet_dir) {
+			watched.push(tests_path);
+		}
+	}
+
+	// Don't re-run the tests when build artifacts are written into the
+	// paclet directory.
+	let watcher = watch::Watcher::new(&watched, vec![paclet_dir.join("build")]);
+
+	// Keep a warm testing subkernel running in between test runs, so that
+	// re-running the tests doesn't wait on Kernel startup.
+	options.push(Expr::rule(Expr::string("WarmKernel"), Expr::from(true)));
+
+	let mut kernel = launch_wolfram_cli_kernel();
+
+	let default_tests = tests_path.as_deref().map(path_to_expr);
+
+	let mut tests = default_tests.clone();
+	let mut prioritized: Vec<PathBuf> = Vec::new();
+
+	loop {
+		let mut run_options = options.clone();
+
+		if !prioritized.is_empty() {
+			run_options.push(Expr::rule(
+				Expr::string("PrioritizedFiles"),
+				Expr::list(
+					prioritized.iter().map(|p| path_to_expr(p)).collect(),
+				),
+			));
+		}
+
+		let _ = run_paclet_tests(
+			&mut kernel,
+			&paclet_dir,
+			tests.clone(),
+			run_options,
+			diff,
+		);
+
+		println!(
+			"\n{}",
+			"Watching for changes... (press Ctrl-C to exit)".dimmed()
+		);
+
+		let changed = watcher.wait_for_changes();
+
+		// Test files that changed and are part of the selected tests.
+		let changed_tests: Vec<PathBuf> = changed
+			.iter()
+			.filter(|path| is_test_file(path) && path.is_file())
+			.filter(|path| match &tests_path {
+				Some(tests_path) => path.starts_with(tests_path),
+				None => true,
+			})
+			.cloned()
+			.collect();
+
+		let only_tests_changed = changed.iter().all(|path| is_test_file(path));
+
+		(tests, prioritized) = match rerun {
+			WatchRerun::Affected if only_tests_changed => {
+				if changed_tests.is_empty() {
+					// Only test files outside of the selected tests changed
+					// (or were deleted), so there is nothing to re-run.
+					continue;
+				}
+
+				let files =
+					changed_tests.iter().map(|p| path_to_expr(p)).collect();
+
+				(Some(Expr::list(files)), Vec::new())
+			},
+			WatchRerun::Affected | WatchRerun::All => {
+				(default_tests.clone(), changed_tests)
+			},
+		};
+
+		// Clear the terminal and move the cursor to the top left.
+		print!("\x1b[2J\x1b[H");
+		std::io::stdout().flush().unwrap();
+	}
+}
+
+/// Summary of a test run, as returned by `CommandPacletTest`.
+struct TestSummary {
+	test_count: usize,
+	failure_count: usize,
+}
+
+impl TestSummary {
+	/// Parse an association of the form
+	/// `<| "TestCount" -> _Integer, "FailureCount" -> _Integer |>`.
+	fn from_expr(expr: &Expr) -> Option<TestSummary> {
+		Some(TestSummary {
+			test_count: lookup_count(expr, "TestCount")?,
+			failure_count: lookup_count(expr, "FailureCount")?,
+		})
+	}
+}
+
+/// Look up the non-negative integer value of `key` in `expr`, which should be
+/// an association.
+fn lookup_count(expr: &Expr, key: &str) -> Option<usize> {
+	match lookup(expr, key)?.kind() {
+		ExprKind::Integer(value) => usize::try_from(*value).ok(),
+		_ => None,
+	}
+}
+
+/// Get the value of the string `key` in the association `expr`.
+fn lookup<'e>(expr: &'e Expr, key: &str) -> Option<&'e Expr> {
+	let normal = expr.try_as_normal()?;
+
+	if !normal.has_head(&Symbol::new("System`Association")) {
+		return None;
+	}
+
+	normal.elements().iter().find_map(|rule| {
+		let rule = rule.try_as_normal()?;
+
+		match rule.elements() {
+			[lhs, rhs] if lhs.try_as_str() == Some(key) => Some(rhs),
+			_ => None,
+		}
+	})
+}
+
+/// Run the tests for `paclet_dir`, returning a summary of the test run, or
+/// `None` if the tests could not be run.
+fn run_paclet_tests(
+	kernel: &mut WolframSession,
+	paclet_dir: &Path,
+	tests_path: Option<Expr>,
+	options: Vec<Expr>,
+	diff: DiffOptions,
+) -> Option<TestSummary> {
+	let mut args = vec![path_to_expr(paclet_dir)];
+
+	//
+	// Optional arguments
+	//
+
+	if let Some(tests_path) = tests_path {
+		args.push(tests_path);
+	}
+
+	args.extend(options);
+
+	// Evaluate:
+	//
+	//     CommandPacletTest[paclet_dir, tests_path, options]
+	evaluate_test_command(
+		kernel,
+		Expr::normal(
+			Symbol::new("ConnorGray`WolframCLI`CommandPacletTest"),
+			args,
+		),
+		diff,
+	)
+}
+
+/// Evaluate a `CommandPacletTest[..]` or `CommandTest[..]` expression,
+/// returning the summary of the test run.
+fn evaluate_test_command(
+	kernel: &mut WolframSession,
+	command: Expr,
+	diff: DiffOptions,
+) -> Option<TestSummary> {
+	let mut progress = Progress::new();
+
+	let outcome = kernel
+		.enter_and_wait_with_output_handler(command, &mut |output| {
+			print_test_output(output, &diff, &mut progress)
+		});
+
+	progress.clear();
 
 	match outcome {
-		EvaluationOutcome::Null => (),
-		EvaluationOutcome::Returned(returned) => {
-			todo!("unexpected return value: {returned:?}")
+		EvaluationOutcome::Null => None,
+		EvaluationOutcome::Returned(returned) => match returned {
+			PacketExpr::Expr(expr) => TestSummary::from_expr(&expr),
+			PacketExpr::Text(_) => None,
 		},
 		EvaluationOutcome::KernelQuit => {
 			todo!("Kernel unexpectedly quit")
 		},
-	};
+	}
+}
+
+/// Exit with a non-zero exit code if any tests failed, or if the tests could
+/// not be run.
+fn exit_if_tests_failed(summary: &Option<TestSummary>) {
+	match summary {
+		Some(TestSummary {
+			failure_count: 0, ..
+		}) => (),
+		Some(_) | None => std::process::exit(1),
+	}
+}
+
+/// Whether `path` names an MUnit test file.
+fn is_test_file(path: &Path) -> bool {
+	match path.extension().and_then(|ext| ext.to_str()) {
+		Some(ext) => ext == "wlt" || ext == "mt",
+		None => false,
+	}
 }
 
 //======================================
-// $ wolfram paclet test [PACLET_DIR]
+// $ wolfram paclet info [PACLET] [--json]
 //======================================
 
-fn handle_paclet_test(
-	paclet_dir: Option<PathBuf>,
-	tests_path: Option<PathBuf>,
-	diff_context: Option<usize>,
-) {
-	let paclet_dir = unwrap_path_or_default_to_current_dir(paclet_dir);
-	let paclet_dir: &str = match paclet_dir.to_str() {
-		Some(paclet_dir) => paclet_dir,
-		None => panic!("paclet directory path is not valid UTF-8"),
+/// Maximum number of files of each extension to list in the output of
+/// `$ wolfram-cli paclet info` (without `--json`).
+const INFO_MAX_LISTED_FILES: usize = 10;
+
+fn handle_paclet_info(paclet: Option<PathBuf>, json: bool) {
+	if let Some(paclet) = &paclet {
+		paths::require_exists(paclet, "paclet", PathKind::Any);
+	}
+
+	let paclet = unwrap_path_or_default_to_current_dir(paclet);
+
+	let info = match paclet_info::PacletInfo::read(&paclet) {
+		Ok(info) => info,
+		Err(err) => {
+			eprintln!("{} {err}", "error:".red().bold());
+			std::process::exit(1);
+		},
 	};
 
-	let mut kernel = kernel::launch_kernel();
+	if json {
+		println!(
+			"{}",
+			serde_json::to_string_pretty(&info)
+				.expect("error serializing paclet metadata")
+		);
+		return;
+	}
 
-	match kernel.packets().next() {
-		Some(Packet::InputName(_)) => (),
-		other => panic!("unexpected WolframKernel first packet: {other:?}"),
+	let paclet_info::PacletInfo {
+		name,
+		version,
+		publisher,
+		wolfram_version,
+		description,
+		dependencies,
+		extensions,
+	} = info;
+
+	let field = |label: &str, value: Option<String>| {
+		if let Some(value) = value {
+			println!("{:<16}{value}", format!("{label}:").bold());
+		}
 	};
 
-	load_wolfram_cli_paclet(&mut kernel);
+	field("Name", Some(name));
+	field("Version", version);
+	field("Publisher", publisher);
+	field("WolframVersion", wolfram_version);
+	field("Description", description.filter(|desc| !desc.is_empty()));
 
-	let mut args = vec![Expr::string(paclet_dir)];
+	if !dependencies.is_empty() {
+		println!("\n{}", "Dependencies:".bold().underline());
 
-	//
-	// Optional arguments
-	//
+		for dependency in dependencies {
+			match dependency.version {
+				Some(version) => println!("\t{} ({version})", dependency.name),
+				None => println!("\t{}", dependency.name),
+			}
+		}
+	}
 
-	if let Some(tests_path) = tests_path {
-		let tests_path = tests_path
-			.to_str()
-			.expect("paclet tests path is not valid UTF-8");
-		args.push(Expr::string(tests_path));
+	if !extensions.is_empty() {
+		println!("\n{}", "Extensions:".bold().underline());
+
+		for extension in extensions {
+			let options: Vec<String> = extension
+				.options
+				.iter()
+				.map(|(key, value)| format!("{key} -> {value}"))
+				.collect();
+
+			println!(
+				"\t{} {}",
+				extension.kind.bold(),
+				format!("{{{}}}", options.join(", ")).dimmed()
+			);
+
+			for file in extension.files.iter().take(INFO_MAX_LISTED_FILES) {
+				println!("\t\t{file}");
+			}
+
+			if extension.files.len() > INFO_MAX_LISTED_FILES {
+				println!(
+					"\t\t{}",
+					format!(
+						"... and {} more files",
+						extension.files.len() - INFO_MAX_LISTED_FILES
+					)
+					.dimmed()
+				);
+			}
+		}
 	}
+}
 
-	//
-	// Options
-	//
+//======================================
+// $ wolfram paclet bench [PACLET_DIR] [BENCH_PATH]
+//======================================
+
+fn handle_paclet_bench(args: PacletBenchArgs) {
+	let PacletBenchArgs {
+		paclet_dir,
+		bench_path,
+		warmup,
+		repetitions,
+		baseline,
+		save_baseline,
+		threshold,
+	} = args;
+
+	if let Some(paclet_dir) = &paclet_dir {
+		paths::require_exists(
+			paclet_dir,
+			"paclet directory",
+			PathKind::Directory,
+		);
+	}
+
+	if let Some(bench_path) = &bench_path {
+		paths::require_exists(bench_path, "benchmarks path", PathKind::Any);
+	}
+
+	let paclet_dir = unwrap_path_or_default_to_current_dir(paclet_dir);
+
+	let mut args = vec![path_to_expr(&paclet_dir)];
+
+	if let Some(bench_path) = bench_path {
+		args.push(path_to_expr(&bench_path));
+	}
+
+	args.push(Expr::rule(
+		Expr::string("Warmup"),
+		Expr::from(i64::from(warmup)),
+	));
+	args.push(Expr::rule(
+		Expr::string("Repetitions"),
+		Expr::from(i64::from(repetitions)),
+	));
+	args.push(Expr::rule(Expr::string("Threshold"), Expr::real(threshold)));
+
+	if let Some(baseline) = baseline {
+		args.push(Expr::rule(Expr::string("Baseline"), Expr::string(baseline)));
+	}
 
-	if let Some(diff_context) = diff_context {
+	if let Some(save_baseline) = save_baseline {
 		args.push(Expr::rule(
-			Expr::string("DiffContext"),
-			Expr::from(
-				i64::try_from(diff_context)
-					.expect("diff context overflows i64"),
-			),
+			Expr::string("SaveBaseline"),
+			Expr::string(save_baseline),
 		));
 	}
 
+	let mut kernel = launch_wolfram_cli_kernel();
 
 	// Evaluate:
 	//
-	//     CommandPacletTest[paclet_dir]
+	//     CommandPacletBench[paclet_dir, bench_path, options]
 	let outcome = kernel.enter_and_wait_with_output_handler(
 		Expr::normal(
-			Symbol::new("ConnorGray`WolframCLI`CommandPacletTest"),
+			Symbol::new("ConnorGray`WolframCLI`CommandPacletBench"),
 			args,
 		),
 		&mut print_command_output,
 	);
 
-	match outcome {
-		EvaluationOutcome::Null => (),
-		EvaluationOutcome::Returned(returned) => {
-			todo!("unexpected return value: {returned:?}")
+	let (regression_count, failed_file_count) = match outcome {
+		EvaluationOutcome::Null => (None, None),
+		EvaluationOutcome::Returned(returned) => match returned {
+			PacketExpr::Expr(expr) => (
+				lookup_count(&expr, "RegressionCount"),
+				lookup_count(&expr, "FailedFileCount"),
+			),
+			PacketExpr::Text(_) => (None, None),
 		},
 		EvaluationOutcome::KernelQuit => {
-			todo!("Kernel unexpectedly quit")
+			eprintln!(
+				"{} Wolfram Kernel quit unexpectedly",
+				"error:".red().bold()
+			);
+			std::process::exit(1);
 		},
 	};
+
+	// Exit with a non-zero exit code if any benchmarks regressed, or if the
+	// benchmarks or any benchmark file could not be run.
+	if regression_count != Some(0) || failed_file_count != Some(0) {
+		std::process::exit(1);
+	}
+}
+
+//======================================
+// $ wolfram test <TESTS_PATHS>...
+//======================================
+
+fn handle_test(args: TestArgs) {
+	let TestArgs {
+		tests_paths,
+		load,
+		run,
+	} = args;
+
+	for tests_path in &tests_paths {
+		paths::require_exists(tests_path, "tests path", PathKind::Any);
+	}
+
+	for dir in &load {
+		paths::require_exists(dir, "load directory", PathKind::Directory);
+	}
+
+	let (mut options, diff) = run.into_options();
+
+	if !load.is_empty() {
+		options.push(Expr::rule(
+			Expr::string("LoadDirectories"),
+			Expr::list(load.iter().map(|dir| path_to_expr(dir)).collect()),
+		));
+	}
+
+	let mut args = vec![Expr::list(
+		tests_paths.iter().map(|path| path_to_expr(path)).collect(),
+	)];
+
+	args.extend(options);
+
+	let mut kernel = launch_wolfram_cli_kernel();
+
+	// Evaluate:
+	//
+	//     CommandTest[{tests_paths...}, options]
+	let summary = evaluate_test_command(
+		&mut kernel,
+		Expr::normal(Symbol::new("ConnorGray`WolframCLI`CommandTest"), args),
+		diff,
+	);
+
+	exit_if_tests_failed(&summary);
 }
 
 //==========================================================
@@ -767,6 +2571,23 @@ fn handle_print_terminal_form_debug_command() {
 // Helpers
 //==========================================================
 
+/// Launch a WolframKernel and load the `ConnorGray/WolframCLI` paclet into it.
+fn launch_wolfram_cli_kernel() -> WolframSession {
+	init_wolfram_cli_kernel(kernel::launch_kernel())
+}
+
+/// Load the `ConnorGray/WolframCLI` paclet into a newly launched `kernel`.
+fn init_wolfram_cli_kernel(mut kernel: WolframSession) -> WolframSession {
+	match kernel.packets().next() {
+		Some(Packet::InputName(_)) => (),
+		other => panic!("unexpected WolframKernel first packet: {other:?}"),
+	};
+
+	load_wolfram_cli_paclet(&mut kernel);
+
+	kernel
+}
+
 fn load_wolfram_cli_paclet(kernel: &mut WolframSession) {
 	// Evaluate:
 	//
@@ -822,9 +2643,196 @@ fn print_command_output(output: wolfram_client::Output) {
 	}
 }
 
+/// Print output generated by the Kernel during a test run, rendering any
+/// events sent by `CommandPacletTest`, and keeping the display of the
+/// `progress` of the test run up to date.
+fn print_test_output(
+	output: wolfram_client::Output,
+	diff: &DiffOptions,
+	progress: &mut Progress,
+) {
+	progress.clear();
+
+	let event = match &output {
+		wolfram_client::Output::Print(PacketExpr::Text(text)) => {
+			event::parse(text)
+		},
+		_ => None,
+	};
+
+	match event {
+		Some(event::Event::TestDiff { expected, actual }) => {
+			diff::print_diff(&expected, &actual, diff)
+		},
+		Some(event) => progress.update(event),
+		None => print_command_output(output),
+	}
+
+	progress.draw();
+}
+
+/// Ask the user to answer yes or no to `question`, returning `true` only if
+/// they answered yes.
+fn confirm(question: &str) -> bool {
+	print!("{question} [y/N] ");
+	std::io::stdout().flush().unwrap();
+
+	let mut answer = String::new();
+
+	if std::io::stdin().read_line(&mut answer).is_err() {
+		return false;
+	}
+
+	matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Load the workspace containing the current directory.
+fn load_workspace() -> workspace::Workspace {
+	let workspace = match workspace::Workspace::discover() {
+		Ok(workspace) => workspace,
+		Err(err) => {
+			eprintln!("{} {err}", "error:".red().bold());
+			std::process::exit(1);
+		},
+	};
+
+	if workspace.members.is_empty() {
+		eprintln!(
+			"{} workspace has no members: {}",
+			"error:".red().bold(),
+			workspace.root.join(workspace::MANIFEST_FILE).display()
+		);
+		std::process::exit(1);
+	}
+
+	workspace
+}
+
+/// Launch a WolframKernel with the `ConnorGray/WolframCLI` paclet and the
+/// paclets in `dirs` loaded, so that the paclets in a workspace resolve
+/// against each other.
+fn launch_workspace_kernel(dirs: &[PathBuf]) -> WolframSession {
+	let mut kernel = launch_wolfram_cli_kernel();
+
+	// Evaluate:
+	//
+	//     Scan[PacletDirectoryLoad, {dirs...}]
+	let outcome = kernel.enter_and_wait_with_output_handler(
+		Expr::normal(
+			Symbol::new("System`Scan"),
+			vec![
+				Expr::symbol(Symbol::new("System`PacletDirectoryLoad")),
+				Expr::list(dirs.iter().map(|dir| path_to_expr(dir)).collect()),
+			],
+		),
+		&mut print_command_output,
+	);
+
+	outcome.unwrap_null();
+
+	kernel
+}
+
+fn print_workspace_member_header(action: &str, member: &workspace::Member) {
+	println!(
+		"\n{}",
+		format!("{action} {} ({})", member.info.name, member.dir.display())
+			.bold()
+	);
+}
+
+/// Print the result of a command for each member of `workspace`, and exit
+/// with a non-zero exit code if the command failed for any member.
+fn print_workspace_summary(
+	workspace: &workspace::Workspace,
+	results: &[Result<String, String>],
+) {
+	let width = workspace
+		.members
+		.iter()
+		.map(|member| member.info.name.len())
+		.max()
+		.unwrap_or(0);
+
+	println!("\n{}", "Results by workspace member:".bold());
+
+	for (member, result) in workspace.members.iter().zip(results) {
+		let name = &member.info.name;
+
+		match result {
+			Ok(detail) => {
+				println!("  {name:<width$}   {}  {detail}", "ok".green())
+			},
+			Err(detail) => {
+				println!("  {name:<width$}   {}  {detail}", "FAILED".red())
+			},
+		}
+	}
+
+	let failed = results.iter().filter(|result| result.is_err()).count();
+
+	if failed > 0 {
+		println!(
+			"\n{}",
+			format!("{failed} of {} paclets failed", results.len()).red()
+		);
+		std::process::exit(1);
+	}
+}
+
+fn path_to_expr(path: &Path) -> Expr {
+	match path.to_str() {
+		Some(path) => Expr::string(path),
+		None => panic!("path is not valid UTF-8: {}", path.display()),
+	}
+}
+
 fn unwrap_path_or_default_to_current_dir(path: Option<PathBuf>) -> PathBuf {
 	path.unwrap_or_else(|| {
 		std::env::current_dir()
 			.expect("unable to get process current working directory")
 	})
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_looks_like_path() {
+		for arg in [
+			"./MyPaclet",
+			"../MyPaclet",
+			".",
+			"/tmp/MyPaclet",
+			"a/b/c",
+			"a\\b",
+		] {
+			assert!(looks_like_path(arg), "{arg:?} should look like a path");
+		}
+
+		for arg in ["MyPaclet", "Wolfram/ErrorTools", "Wolfram/ErrorTools@1.0"]
+		{
+			assert!(
+				!looks_like_path(arg),
+				"{arg:?} should look like a paclet name"
+			);
+		}
+	}
+
+	#[test]
+	fn test_paclet_test_isolate_alias() {
+		for flag in ["--disable-installed", "--isolate"] {
+			let cli =
+				Cli::try_parse_from(["wolfram-cli", "paclet", "test", flag])
+					.unwrap();
+
+			match cli.command {
+				Some(Command::Paclet(PacletCommand::Test(args))) => {
+					assert!(args.disable_installed, "{flag}")
+				},
+				_ => panic!("expected `paclet test` command"),
+			}
+		}
+	}
+}
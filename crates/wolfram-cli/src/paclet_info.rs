@@ -0,0 +1,965 @@
+//! Native parsing of paclet `PacletInfo.wl` files.
+//!
+//! Paclet metadata files are Wolfram Language expressions of the form
+//! `PacletObject[<| ... |>]` (or, in older `PacletInfo.m` files,
+//! `Paclet[Name -> ..., ...]`). They use only a small subset of the language,
+//! which is parsed here so that paclet metadata can be read without launching
+//! a Kernel.
+
+use std::{
+	fmt::{self, Display},
+	io::Read,
+	path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+//==========================================================
+// Expressions
+//==========================================================
+
+/// A Wolfram Language expression, restricted to the forms used in paclet
+/// metadata files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+	String(String),
+	Integer(i64),
+	Real(f64),
+	Symbol(String),
+	List(Vec<Value>),
+	Association(Vec<(Value, Value)>),
+	/// `lhs -> rhs` or `lhs :> rhs`.
+	Rule(Box<Value>, Box<Value>),
+	Normal(Box<Value>, Vec<Value>),
+}
+
+impl Value {
+	/// The name of a key given as either a string or a symbol, e.g. `"Root"`
+	/// or `Root`.
+	fn as_key(&self) -> Option<&str> {
+		match self {
+			Value::String(key) | Value::Symbol(key) => Some(key),
+			_ => None,
+		}
+	}
+
+	fn to_json(&self) -> serde_json::Value {
+		use serde_json::Value as Json;
+
+		match self {
+			Value::String(string) => Json::from(string.as_str()),
+			Value::Integer(int) => Json::from(*int),
+			Value::Real(real) => Json::from(*real),
+			Value::Symbol(symbol) => match symbol.as_str() {
+				"True" => Json::Bool(true),
+				"False" => Json::Bool(false),
+				"None" | "Null" => Json::Null,
+				_ => Json::from(symbol.as_str()),
+			},
+			Value::List(elements) => {
+				Json::Array(elements.iter().map(Value::to_json).collect())
+			},
+			Value::Association(rules) => Json::Object(
+				rules
+					.iter()
+					.map(|(key, value)| (key_string(key), value.to_json()))
+					.collect(),
+			),
+			Value::Rule(lhs, rhs) => Json::Object(
+				[(key_string(lhs), rhs.to_json())].into_iter().collect(),
+			),
+			Value::Normal(..) => Json::from(self.to_string()),
+		}
+	}
+}
+
+fn key_string(key: &Value) -> String {
+	match key.as_key() {
+		Some(key) => key.to_owned(),
+		None => key.to_string(),
+	}
+}
+
+impl Display for Value {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fn write_sequence(
+			f: &mut fmt::Formatter,
+			elements: &[Value],
+		) -> fmt::Result {
+			for (index, element) in elements.iter().enumerate() {
+				if index > 0 {
+					write!(f, ", ")?;
+				}
+				write!(f, "{element}")?;
+			}
+			Ok(())
+		}
+
+		match self {
+			Value::String(string) => write!(f, "{string:?}"),
+			Value::Integer(int) => write!(f, "{int}"),
+			Value::Real(real) => write!(f, "{real}"),
+			Value::Symbol(symbol) => write!(f, "{symbol}"),
+			Value::List(elements) => {
+				write!(f, "{{")?;
+				write_sequence(f, elements)?;
+				write!(f, "}}")
+			},
+			Value::Association(rules) => {
+				write!(f, "<|")?;
+				for (index, (key, value)) in rules.iter().enumerate() {
+					if index > 0 {
+						write!(f, ", ")?;
+					}
+					write!(f, "{key} -> {value}")?;
+				}
+				write!(f, "|>")
+			},
+			Value::Rule(lhs, rhs) => write!(f, "{lhs} -> {rhs}"),
+			Value::Normal(head, args) => {
+				write!(f, "{head}[")?;
+				write_sequence(f, args)?;
+				write!(f, "]")
+			},
+		}
+	}
+}
+
+//==========================================================
+// Parsing
+//==========================================================
+
+/// Parse the text of a paclet metadata file.
+pub fn parse(text: &str) -> Result<Value, String> {
+	let mut parser = Parser {
+		chars: text.chars().collect(),
+		position: 0,
+	};
+
+	let value = parser.parse_expr()?;
+
+	parser.skip_whitespace()?;
+
+	if parser.position < parser.chars.len() {
+		return Err(parser.error("unexpected input after expression"));
+	}
+
+	Ok(value)
+}
+
+struct Parser {
+	chars: Vec<char>,
+	position: usize,
+}
+
+impl Parser {
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.position).copied()
+	}
+
+	fn starts_with(&self, token: &str) -> bool {
+		token.chars().enumerate().all(|(offset, char)| {
+			self.chars.get(self.position + offset) == Some(&char)
+		})
+	}
+
+	fn error(&self, message: &str) -> String {
+		let line = self.chars[..self.position.min(self.chars.len())]
+			.iter()
+			.filter(|char| **char == '\n')
+			.count() + 1;
+
+		format!("{message} (line {line})")
+	}
+
+	/// Skip whitespace and (possibly nested) comments.
+	fn skip_whitespace(&mut self) -> Result<(), String> {
+		loop {
+			if self.peek().is_some_and(char::is_whitespace) {
+				self.position += 1;
+			} else if self.starts_with("(*") {
+				let mut depth = 0;
+
+				loop {
+					if self.starts_with("(*") {
+						depth += 1;
+						self.position += 2;
+					} else if self.starts_with("*)") {
+						depth -= 1;
+						self.position += 2;
+
+						if depth == 0 {
+							break;
+						}
+					} else if self.peek().is_some() {
+						self.position += 1;
+					} else {
+						return Err(self.error("unterminated comment"));
+					}
+				}
+			} else {
+				return Ok(());
+			}
+		}
+	}
+
+	/// Consume `token` if it is next in the input.
+	fn eat(&mut self, token: &str) -> Result<bool, String> {
+		self.skip_whitespace()?;
+
+		if self.starts_with(token) {
+			self.position += token.chars().count();
+			Ok(true)
+		} else {
+			Ok(false)
+		}
+	}
+
+	fn expect(&mut self, token: &str) -> Result<(), String> {
+		if !self.eat(token)? {
+			return Err(self.error(&format!("expected '{token}'")));
+		}
+		Ok(())
+	}
+
+	fn parse_expr(&mut self) -> Result<Value, String> {
+		let lhs = self.parse_primary()?;
+
+		if self.eat("->")? || self.eat(":>")? {
+			let rhs = self.parse_expr()?;
+			return Ok(Value::Rule(Box::new(lhs), Box::new(rhs)));
+		}
+
+		Ok(lhs)
+	}
+
+	fn parse_primary(&mut self) -> Result<Value, String> {
+		self.skip_whitespace()?;
+
+		let mut value = match self.peek() {
+			Some('"') => self.parse_string()?,
+			Some('{') => {
+				self.position += 1;
+				Value::List(self.parse_sequence("}")?)
+			},
+			Some('<') if self.starts_with("<|") => {
+				self.position += 2;
+
+				let rules = self
+					.parse_sequence("|>")?
+					.into_iter()
+					.map(|rule| match rule {
+						Value::Rule(key, value) => Ok((*key, *value)),
+						other => Err(format!(
+							"expected rule in association, found: {other}"
+						)),
+					})
+					.collect::<Result<_, _>>()?;
+
+				Value::Association(rules)
+			},
+			Some(char) if char.is_ascii_digit() || char == '-' => {
+				self.parse_number()?
+			},
+			Some(char)
+				if char.is_alphabetic() || char == '$' || char == '`' =>
+			{
+				let start = self.position;
+				while self.peek().is_some_and(|char| {
+					char.is_alphanumeric() || char == '$' || char == '`'
+				}) {
+					self.position += 1;
+				}
+				Value::Symbol(self.chars[start..self.position].iter().collect())
+			},
+			Some(char) => {
+				return Err(
+					self.error(&format!("unexpected character {char:?}"))
+				)
+			},
+			None => return Err(self.error("unexpected end of input")),
+		};
+
+		// Parse any number of `[args]` following the value.
+		while self.eat("[")? {
+			let args = self.parse_sequence("]")?;
+			value = Value::Normal(Box::new(value), args);
+		}
+
+		Ok(value)
+	}
+
+	/// Parse comma-separated expressions up to and including `close`.
+	fn parse_sequence(&mut self, close: &str) -> Result<Vec<Value>, String> {
+		let mut elements = Vec::new();
+
+		if self.eat(close)? {
+			return Ok(elements);
+		}
+
+		loop {
+			elements.push(self.parse_expr()?);
+
+			if self.eat(close)? {
+				return Ok(elements);
+			}
+
+			self.expect(",")?;
+		}
+	}
+
+	fn parse_string(&mut self) -> Result<Value, String> {
+		// Skip the opening quote.
+		self.position += 1;
+
+		let mut string = String::new();
+
+		loop {
+			match self.peek() {
+				Some('"') => {
+					self.position += 1;
+					return Ok(Value::String(string));
+				},
+				Some('\\') => {
+					self.position += 1;
+					match self.peek() {
+						Some('n') => string.push('\n'),
+						Some('t') => string.push('\t'),
+						Some(char) => string.push(char),
+						None => break,
+					}
+					self.position += 1;
+				},
+				Some(char) => {
+					string.push(char);
+					self.position += 1;
+				},
+				None => break,
+			}
+		}
+
+		Err(self.error("unterminated string"))
+	}
+
+	fn parse_number(&mut self) -> Result<Value, String> {
+		let start = self.position;
+
+		if self.peek() == Some('-') {
+			self.position += 1;
+		}
+
+		while self
+			.peek()
+			.is_some_and(|char| char.is_ascii_digit() || char == '.')
+		{
+			self.position += 1;
+		}
+
+		let text: String = self.chars[start..self.position].iter().collect();
+
+		let value = match text.contains('.') {
+			true => text.parse().map(Value::Real).ok(),
+			false => text.parse().map(Value::Integer).ok(),
+		};
+
+		value.ok_or_else(|| self.error(&format!("invalid number: {text}")))
+	}
+}
+
+//==========================================================
+// Paclet metadata
+//==========================================================
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PacletInfo {
+	pub name: String,
+	pub version: Option<String>,
+	pub publisher: Option<String>,
+	pub wolfram_version: Option<String>,
+	pub description: Option<String>,
+	pub dependencies: Vec<Dependency>,
+	pub extensions: Vec<Extension>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Dependency {
+	pub name: String,
+	/// Required version of the dependency, e.g. `"1.0+"`.
+	pub version: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Extension {
+	#[serde(rename = "Type")]
+	pub kind: String,
+	#[serde(serialize_with = "serialize_options")]
+	pub options: Vec<(String, Value)>,
+	/// Directory containing the files of this extension, relative to the
+	/// paclet root directory.
+	pub root: Option<String>,
+	/// Files in the extension root directory, relative to the paclet root
+	/// directory.
+	pub files: Vec<String>,
+}
+
+fn serialize_options<S: serde::Serializer>(
+	options: &[(String, Value)],
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	let map: serde_json::Map<String, serde_json::Value> = options
+		.iter()
+		.map(|(key, value)| (key.clone(), value.to_json()))
+		.collect();
+
+	map.serialize(serializer)
+}
+
+impl PacletInfo {
+	/// Read the metadata of the paclet in `path`, which is either a paclet
+	/// directory or a `.paclet` archive file.
+	pub fn read(path: &Path) -> Result<PacletInfo, String> {
+		if path.is_dir() {
+			PacletInfo::read_dir(path)
+		} else {
+			PacletInfo::read_archive(path)
+		}
+	}
+
+	fn read_dir(dir: &Path) -> Result<PacletInfo, String> {
+		let file = ["PacletInfo.wl", "PacletInfo.m"]
+			.into_iter()
+			.map(|name| dir.join(name))
+			.find(|file| file.is_file())
+			.ok_or_else(|| {
+				format!(
+					"no PacletInfo.wl or PacletInfo.m file found in directory: {}",
+					dir.display()
+				)
+			})?;
+
+		let text = std::fs::read_to_string(&file).map_err(|err| {
+			format!("error reading {}: {err}", file.display())
+		})?;
+
+		let mut info = PacletInfo::from_text(&text).map_err(|err| {
+			format!("error parsing {}: {err}", file.display())
+		})?;
+
+		for extension in &mut info.extensions {
+			if let Some(root) = &extension.root {
+				extension.files = dir_files(dir, &dir.join(root));
+			}
+		}
+
+		Ok(info)
+	}
+
+	fn read_archive(path: &Path) -> Result<PacletInfo, String> {
+		let archive_error = |err| {
+			format!("error reading paclet archive {}: {err}", path.display())
+		};
+
+		let file = std::fs::File::open(path)
+			.map_err(|err| archive_error(err.to_string()))?;
+
+		let mut archive = zip::ZipArchive::new(file)
+			.map_err(|err| archive_error(err.to_string()))?;
+
+		// The paclet root is the directory in the archive that contains the
+		// PacletInfo file.
+		let info_file = archive
+			.file_names()
+			.filter(|name| {
+				name.ends_with("PacletInfo.wl")
+					|| name.ends_with("PacletInfo.m")
+			})
+			.min_by_key(|name| name.matches('/').count())
+			.map(str::to_owned)
+			.ok_or_else(|| {
+				archive_error("no PacletInfo.wl file found".into())
+			})?;
+
+		let prefix = match info_file.rfind('/') {
+			Some(index) => info_file[..=index].to_owned(),
+			None => String::new(),
+		};
+
+		let mut text = String::new();
+		archive
+			.by_name(&info_file)
+			.and_then(|mut file| Ok(file.read_to_string(&mut text)?))
+			.map_err(|err| archive_error(err.to_string()))?;
+
+		let mut info = PacletInfo::from_text(&text)
+			.map_err(|err| format!("error parsing {info_file}: {err}"))?;
+
+		let files: Vec<String> = archive
+			.file_names()
+			.filter_map(|name| name.strip_prefix(&prefix))
+			.filter(|name| !name.is_empty() && !name.ends_with('/'))
+			.map(str::to_owned)
+			.collect();
+
+		for extension in &mut info.extensions {
+			if let Some(root) = &extension.root {
+				let root = normalize_root(root);
+
+				let mut extension_files: Vec<String> = files
+					.iter()
+					.filter(|file| {
+						root.is_empty() || file.starts_with(&format!("{root}/"))
+					})
+					.cloned()
+					.collect();
+				extension_files.sort();
+
+				extension.files = extension_files;
+			}
+		}
+
+		Ok(info)
+	}
+
+	/// Parse the text of a paclet metadata file.
+	pub fn from_text(text: &str) -> Result<PacletInfo, String> {
+		let fields: Vec<(String, Value)> = match parse(text)? {
+			// PacletObject[<| key -> value, ... |>]
+			Value::Normal(head, args) => match (*head, args.as_slice()) {
+				(Value::Symbol(head), [Value::Association(rules)])
+					if head == "PacletObject" =>
+				{
+					rules
+						.iter()
+						.filter_map(|(key, value)| {
+							Some((key.as_key()?.to_owned(), value.clone()))
+						})
+						.collect()
+				},
+				// Paclet[key -> value, ...]
+				(Value::Symbol(head), rules) if head == "Paclet" => rules
+					.iter()
+					.filter_map(|rule| match rule {
+						Value::Rule(key, value) => {
+							Some((key.as_key()?.to_owned(), (**value).clone()))
+						},
+						_ => None,
+					})
+					.collect(),
+				_ => {
+					return Err(
+						"expected PacletObject[<| ... |>] or Paclet[...]"
+							.into(),
+					)
+				},
+			},
+			_ => {
+				return Err(
+					"expected PacletObject[<| ... |>] or Paclet[...]".into()
+				)
+			},
+		};
+
+		let field = |key: &str| -> Option<&Value> {
+			fields
+				.iter()
+				.find(|(field, _)| field == key)
+				.map(|(_, value)| value)
+		};
+
+		let string_field = |key: &str| -> Option<String> {
+			match field(key)? {
+				Value::String(string) => Some(string.clone()),
+				other => Some(other.to_string()),
+			}
+		};
+
+		let name = string_field("Name")
+			.ok_or_else(|| "paclet has no \"Name\" field".to_owned())?;
+
+		let publisher = string_field("PublisherID").or_else(|| {
+			name.split_once('/')
+				.map(|(publisher, _)| publisher.to_owned())
+		});
+
+		let dependencies = match field("Dependencies") {
+			Some(Value::List(dependencies)) => dependencies
+				.iter()
+				.map(|dependency| match dependency {
+					Value::Rule(name, version) => Dependency {
+						name: key_string(name),
+						version: Some(match &**version {
+							Value::String(version) => version.clone(),
+							other => other.to_string(),
+						}),
+					},
+					other => Dependency {
+						name: key_string(other),
+						version: None,
+					},
+				})
+				.collect(),
+			_ => Vec::new(),
+		};
+
+		let extensions = match field("Extensions") {
+			Some(Value::List(extensions)) => extensions
+				.iter()
+				.filter_map(Extension::from_value)
+				.collect(),
+			_ => Vec::new(),
+		};
+
+		Ok(PacletInfo {
+			name,
+			version: string_field("Version"),
+			publisher,
+			wolfram_version: string_field("WolframVersion")
+				.or_else(|| string_field("MathematicaVersion")),
+			description: string_field("Description"),
+			dependencies,
+			extensions,
+		})
+	}
+}
+
+impl Extension {
+	/// Parse an extension of the form `{"Type", key -> value, ...}`.
+	fn from_value(value: &Value) -> Option<Extension> {
+		let Value::List(elements) = value else {
+			return None;
+		};
+
+		let (kind, options) = elements.split_first()?;
+
+		let kind = kind.as_key()?.to_owned();
+
+		let options: Vec<(String, Value)> = options
+			.iter()
+			.filter_map(|option| match option {
+				Value::Rule(key, value) => {
+					Some((key.as_key()?.to_owned(), (**value).clone()))
+				},
+				_ => None,
+			})
+			.collect();
+
+		let root = options
+			.iter()
+			.find(|(key, _)| key == "Root")
+			.and_then(|(_, value)| match value {
+				Value::String(root) => Some(root.clone()),
+				_ => None,
+			})
+			.or_else(|| default_root(&kind).map(str::to_owned));
+
+		Some(Extension {
+			kind,
+			options,
+			root,
+			files: Vec::new(),
+		})
+	}
+}
+
+/// The directory used by an extension of type `kind` that doesn't specify a
+/// `"Root"`.
+fn default_root(kind: &str) -> Option<&'static str> {
+	match kind {
+		"Kernel" => Some("Kernel"),
+		"Documentation" => Some("Documentation"),
+		"LibraryResources" | "LibraryLink" => Some("LibraryResources"),
+		"FrontEnd" => Some("FrontEnd"),
+		"Tests" => Some("Tests"),
+		"Benchmarks" => Some("Benchmarks"),
+		_ => None,
+	}
+}
+
+/// Convert a `"Root"` path to the form used for files in paclet archives.
+fn normalize_root(root: &str) -> String {
+	match root.trim_start_matches("./").trim_end_matches('/') {
+		"." => String::new(),
+		root => root.replace('\\', "/"),
+	}
+}
+
+/// List the files in `dir`, recursively, relative to `paclet_dir`.
+fn dir_files(paclet_dir: &Path, dir: &Path) -> Vec<String> {
+	fn visit(dir: &Path, files: &mut Vec<PathBuf>) {
+		let Ok(entries) = std::fs::read_dir(dir) else {
+			return;
+		};
+
+		for entry in entries.flatten() {
+			let path = entry.path();
+
+			if path.is_dir() {
+				visit(&path, files);
+			} else {
+				files.push(path);
+			}
+		}
+	}
+
+	let mut files = Vec::new();
+	visit(dir, &mut files);
+
+	let mut files: Vec<String> = files
+		.iter()
+		.filter_map(|file| file.strip_prefix(paclet_dir).ok())
+		.map(|file| file.to_string_lossy().replace('\\', "/"))
+		.collect();
+	files.sort();
+
+	files
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::test_util::TempDir;
+
+	fn string(value: &str) -> Value {
+		Value::String(value.to_owned())
+	}
+
+	fn symbol(value: &str) -> Value {
+		Value::Symbol(value.to_owned())
+	}
+
+	fn rule(lhs: Value, rhs: Value) -> Value {
+		Value::Rule(Box::new(lhs), Box::new(rhs))
+	}
+
+	#[test]
+	fn test_parse_rules() {
+		assert_eq!(
+			parse(r#"{"a" -> 1, b :> "c"}"#),
+			Ok(Value::List(vec![
+				rule(string("a"), Value::Integer(1)),
+				rule(symbol("b"), string("c")),
+			]))
+		);
+
+		// Rules are right-associative.
+		assert_eq!(
+			parse("a -> b -> c"),
+			Ok(rule(symbol("a"), rule(symbol("b"), symbol("c"))))
+		);
+	}
+
+	#[test]
+	fn test_parse_nested_lists() {
+		assert_eq!(
+			parse("{{}, {1, {2.5, -3}}, <|x -> {y}|>}"),
+			Ok(Value::List(vec![
+				Value::List(vec![]),
+				Value::List(vec![
+					Value::Integer(1),
+					Value::List(vec![Value::Real(2.5), Value::Integer(-3)]),
+				]),
+				Value::Association(vec![(
+					symbol("x"),
+					Value::List(vec![symbol("y")])
+				)]),
+			]))
+		);
+	}
+
+	#[test]
+	fn test_parse_strings_and_comments() {
+		assert_eq!(
+			parse(r#""a \"quoted\"\n\ttab \\ backslash""#),
+			Ok(string("a \"quoted\"\n\ttab \\ backslash"))
+		);
+
+		assert_eq!(
+			parse("(* outer (* nested *) comment *) {1, (* inner *) 2}\n(* end *)"),
+			Ok(Value::List(vec![Value::Integer(1), Value::Integer(2)]))
+		);
+
+		// Comment delimiters inside strings are not comments.
+		assert_eq!(
+			parse(r#""(* not a comment *)""#),
+			Ok(string("(* not a comment *)"))
+		);
+	}
+
+	#[test]
+	fn test_parse_normal() {
+		assert_eq!(
+			parse("f[x, g[]][y]"),
+			Ok(Value::Normal(
+				Box::new(Value::Normal(
+					Box::new(symbol("f")),
+					vec![
+						symbol("x"),
+						Value::Normal(Box::new(symbol("g")), vec![])
+					]
+				)),
+				vec![symbol("y")]
+			))
+		);
+	}
+
+	#[test]
+	fn test_parse_malformed() {
+		for text in [
+			"",
+			"{1, 2",
+			"{1 2}",
+			"<|a|>",
+			"\"unterminated",
+			"(* unterminated",
+			"f[",
+			"-",
+			"1.2.3",
+			"{1} extra",
+			"a ->",
+			"#",
+		] {
+			assert!(parse(text).is_err(), "expected error parsing {text:?}");
+		}
+	}
+
+	#[test]
+	fn test_paclet_object() {
+		let info = PacletInfo::from_text(
+			r#"
+			(* Paclet metadata *)
+			PacletObject[<|
+				"Name" -> "Publisher/Example",
+				"Version" -> "1.2.3",
+				"WolframVersion" -> "13.2+",
+				"Description" -> "An \"example\" paclet",
+				"Dependencies" -> {
+					"Wolfram/ErrorTools",
+					"Other/Paclet" -> "1.0+"
+				},
+				"Extensions" -> {
+					{"Kernel", "Root" -> "Source", "Context" -> "Example`"},
+					{"LibraryResources"},
+					{"Custom"},
+					"NotAnExtension"
+				}
+			|>]
+			"#,
+		)
+		.unwrap();
+
+		assert_eq!(info.name, "Publisher/Example");
+		assert_eq!(info.version.as_deref(), Some("1.2.3"));
+		assert_eq!(info.publisher.as_deref(), Some("Publisher"));
+		assert_eq!(info.wolfram_version.as_deref(), Some("13.2+"));
+		assert_eq!(info.description.as_deref(), Some("An \"example\" paclet"));
+
+		let dependencies: Vec<(&str, Option<&str>)> = info
+			.dependencies
+			.iter()
+			.map(|dependency| {
+				(dependency.name.as_str(), dependency.version.as_deref())
+			})
+			.collect();
+
+		assert_eq!(
+			dependencies,
+			[("Wolfram/ErrorTools", None), ("Other/Paclet", Some("1.0+"))]
+		);
+
+		let extensions: Vec<(&str, Option<&str>)> = info
+			.extensions
+			.iter()
+			.map(|extension| {
+				(extension.kind.as_str(), extension.root.as_deref())
+			})
+			.collect();
+
+		assert_eq!(
+			extensions,
+			[
+				("Kernel", Some("Source")),
+				("LibraryResources", Some("LibraryResources")),
+				("Custom", None),
+			]
+		);
+
+		assert_eq!(
+			info.extensions[0].options[1],
+			("Context".to_owned(), string("Example`"))
+		);
+	}
+
+	#[test]
+	fn test_legacy_paclet() {
+		let info = PacletInfo::from_text(
+			r#"Paclet[
+				Name -> "Example",
+				Version -> "0.1",
+				MathematicaVersion -> "10+",
+				Extensions -> {{"Kernel", Context -> "Example`"}}
+			]"#,
+		)
+		.unwrap();
+
+		assert_eq!(info.name, "Example");
+		assert_eq!(info.version.as_deref(), Some("0.1"));
+		assert_eq!(info.publisher, None);
+		assert_eq!(info.wolfram_version.as_deref(), Some("10+"));
+		assert_eq!(info.extensions[0].root.as_deref(), Some("Kernel"));
+	}
+
+	#[test]
+	fn test_invalid_paclet_metadata() {
+		for text in [
+			"{1, 2}",
+			"PacletObject[{}]",
+			"PacletObject[<|\"Version\" -> \"1.0\"|>]",
+			"PacletObject[<|\"Name\" -> \"A\"|>",
+		] {
+			assert!(
+				PacletInfo::from_text(text).is_err(),
+				"expected error reading {text:?}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_normalize_root() {
+		assert_eq!(normalize_root("./Kernel/"), "Kernel");
+		assert_eq!(normalize_root("."), "");
+		assert_eq!(normalize_root("Source\\Kernel"), "Source/Kernel");
+	}
+
+	#[test]
+	fn test_read() {
+		let dir = TempDir::new("paclet-info-read");
+
+		let err = PacletInfo::read(dir.path()).unwrap_err();
+		assert!(
+			err.starts_with("no PacletInfo.wl or PacletInfo.m file found"),
+			"{err}"
+		);
+
+		let archive = dir.write("MyPaclet-1.0.0.paclet", "not a zip file");
+		let err = PacletInfo::read(&archive).unwrap_err();
+		assert!(err.starts_with("error reading paclet archive"), "{err}");
+
+		dir.write(
+			"PacletInfo.wl",
+			r#"PacletObject[<|
+				"Name" -> "MyPaclet",
+				"Version" -> "1.0.0",
+				"Extensions" -> {{"Kernel", "Root" -> "Kernel"}}
+			|>]"#,
+		);
+		dir.write("Kernel/MyPaclet.wl", "");
+
+		let info = PacletInfo::read(dir.path()).unwrap();
+		assert_eq!(info.name, "MyPaclet");
+		assert_eq!(info.extensions[0].files, vec!["Kernel/MyPaclet.wl"]);
+	}
+}
@@ -0,0 +1,190 @@
+//! Diagnostics for paths specified on the command line that don't exist.
+
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use similar::TextDiff;
+
+/// Maximum number of similarly named files to suggest.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Minimum similarity ratio between the name of a missing file and the name of
+/// an existing file for the existing file to be suggested.
+const MIN_SIMILARITY: f32 = 0.6;
+
+/// Kind of file expected at a path specified on the command line.
+#[derive(Debug, Clone, Copy)]
+pub enum PathKind {
+	/// A `.paclet` archive file.
+	PacletFile,
+	/// A directory, e.g. a paclet directory.
+	Directory,
+	/// Any file or directory.
+	Any,
+}
+
+/// Exit with an error describing what is wrong if `path` does not exist.
+///
+/// `description` describes what the path was expected to be, e.g.
+/// `"paclet directory"`. The error states whether the parent directory of
+/// `path` exists, and suggests entries in the parent directory with names
+/// similar to `path`.
+pub fn require_exists(path: &Path, description: &str, kind: PathKind) {
+	if path.exists() {
+		return;
+	}
+
+	eprintln!(
+		"{} {description} does not exist: {}",
+		"error:".red().bold(),
+		path.display()
+	);
+
+	let parent = match path.parent() {
+		Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+		Some(parent) => parent,
+		None => std::process::exit(1),
+	};
+
+	if !parent.is_dir() {
+		eprintln!(
+			"{}",
+			format!(
+				"note: the parent directory {} does not exist either",
+				parent.display()
+			)
+			.dimmed()
+		);
+		std::process::exit(1);
+	}
+
+	let suggestions = similar_entries(path, parent, kind);
+
+	if suggestions.is_empty() {
+		eprintln!(
+			"{}",
+			format!(
+				"note: the parent directory {} exists, but contains no similarly named entries",
+				parent.display()
+			)
+			.dimmed()
+		);
+	} else {
+		eprintln!(
+			"{}",
+			format!(
+				"hint: {} contains similarly named entries:",
+				parent.display()
+			)
+			.dimmed()
+		);
+
+		for suggestion in suggestions {
+			eprintln!("\t{}", parent.join(suggestion).display());
+		}
+	}
+
+	std::process::exit(1);
+}
+
+/// Get the canonical absolute form of `path`, which does not need to exist.
+///
+/// The longest ancestor of `path` that exists is canonicalized, and the
+/// remaining components of `path` are appended to it, so that the result can
+/// be compared against canonical paths before the directory is created.
+pub fn canonicalize_lenient(path: &Path) -> PathBuf {
+	let path = std::env::current_dir()
+		.expect("unable to get process current working directory")
+		.join(path);
+
+	let mut missing = Vec::new();
+	let mut existing = path.as_path();
+
+	loop {
+		if let Ok(canonical) = existing.canonicalize() {
+			return missing
+				.into_iter()
+				.rev()
+				.fold(canonical, |path, component| path.join(component));
+		}
+
+		match (existing.parent(), existing.file_name()) {
+			(Some(parent), Some(name)) => {
+				missing.push(name);
+				existing = parent;
+			},
+			_ => return path,
+		}
+	}
+}
+
+/// Get the names of the entries of `dir` of the specified `kind` whose names are
+/// most similar to the name of `path`.
+fn similar_entries(path: &Path, dir: &Path, kind: PathKind) -> Vec<String> {
+	let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+		return Vec::new();
+	};
+	let name = name.to_lowercase();
+
+	let Ok(entries) = std::fs::read_dir(dir) else {
+		return Vec::new();
+	};
+
+	let mut candidates: Vec<(f32, String)> = entries
+		.filter_map(|entry| entry.ok())
+		.filter(|entry| {
+			let path = entry.path();
+
+			match kind {
+				PathKind::PacletFile => {
+					path.is_file()
+						&& path.extension().is_some_and(|ext| ext == "paclet")
+				},
+				PathKind::Directory => path.is_dir(),
+				PathKind::Any => true,
+			}
+		})
+		.filter_map(|entry| entry.file_name().into_string().ok())
+		.map(|candidate| {
+			let similarity = TextDiff::from_chars(
+				name.as_str(),
+				candidate.to_lowercase().as_str(),
+			)
+			.ratio();
+
+			(similarity, candidate)
+		})
+		.filter(|(similarity, _)| *similarity >= MIN_SIMILARITY)
+		.collect();
+
+	candidates.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+	candidates
+		.into_iter()
+		.take(MAX_SUGGESTIONS)
+		.map(|(_, candidate)| candidate)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::test_util::TempDir;
+
+	#[test]
+	fn test_canonicalize_lenient() {
+		let dir = TempDir::new("paths-canonicalize-lenient");
+		std::fs::create_dir(dir.path().join("a")).unwrap();
+
+		assert_eq!(
+			canonicalize_lenient(&dir.path().join("a/../a/./")),
+			dir.path().join("a")
+		);
+
+		assert_eq!(
+			canonicalize_lenient(&dir.path().join("a/../a/new/build")),
+			dir.path().join("a/new/build")
+		);
+	}
+}
@@ -0,0 +1,150 @@
+//! Live display of the progress of a test run.
+//!
+//! When standard output is a terminal, a status line showing the progress of
+//! the test run is kept below the test output, and redrawn in place whenever
+//! a test finishes. Otherwise, a status line is printed before each test file
+//! is run.
+
+use std::{
+	io::{IsTerminal, Write},
+	path::Path,
+	time::Instant,
+};
+
+use colored::Colorize;
+
+use crate::event::Event;
+
+/// ANSI escape sequence that moves the cursor to the start of the line and
+/// clears the line.
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+pub struct Progress {
+	is_terminal: bool,
+	start: Instant,
+	/// Number of test files to be run, and the number of tests they contain.
+	/// `None` until the test run has started.
+	totals: Option<(usize, usize)>,
+	/// Number of test files started so far.
+	files_started: usize,
+	/// Test file currently being run.
+	file: Option<String>,
+	passed: usize,
+	failed: usize,
+	/// Whether the status line is currently drawn.
+	drawn: bool,
+}
+
+impl Progress {
+	pub fn new() -> Self {
+		Progress {
+			is_terminal: std::io::stdout().is_terminal(),
+			start: Instant::now(),
+			totals: None,
+			files_started: 0,
+			file: None,
+			passed: 0,
+			failed: 0,
+			drawn: false,
+		}
+	}
+
+	/// Update the progress of the test run in response to `event`.
+	pub fn update(&mut self, event: Event) {
+		match event {
+			Event::RunStarted {
+				file_count,
+				test_count,
+			} => {
+				self.totals = Some((file_count, test_count));
+			},
+			Event::FileStarted { file } => {
+				self.files_started += 1;
+				self.file = Some(file);
+
+				if !self.is_terminal {
+					println!("{}", self.status_line(None));
+				}
+			},
+			Event::TestFinished { outcome } => match outcome.as_str() {
+				"Success" | "SnapshotUpdated" => self.passed += 1,
+				_ => self.failed += 1,
+			},
+			Event::TestDiff { .. } => (),
+		}
+	}
+
+	/// Draw the status line below any other output, if standard output is a
+	/// terminal.
+	///
+	/// The status line must be cleared using [`Progress::clear()`] before any
+	/// other output is printed.
+	pub fn draw(&mut self) {
+		if !self.is_terminal || self.totals.is_none() {
+			return;
+		}
+
+		let width = terminal_size::terminal_size()
+			.map(|(terminal_size::Width(width), _)| usize::from(width));
+
+		print!("{}", self.status_line(width));
+		std::io::stdout().flush().unwrap();
+
+		self.drawn = true;
+	}
+
+	/// Clear the status line, if it is drawn.
+	pub fn clear(&mut self) {
+		if self.drawn {
+			print!("{CLEAR_LINE}");
+			std::io::stdout().flush().unwrap();
+
+			self.drawn = false;
+		}
+	}
+
+	/// Format the status line, truncating the name of the current test file
+	/// so that the line fits in `width` columns.
+	fn status_line(&self, width: Option<usize>) -> String {
+		let (file_count, test_count) = self.totals.unwrap_or((0, 0));
+
+		let prefix = format!(
+			"[{}/{file_count} files] [{}/{test_count} tests] {:.1}s ",
+			self.files_started,
+			self.passed + self.failed,
+			self.start.elapsed().as_secs_f64(),
+		);
+		let passed = format!("{} passed", self.passed);
+		let failed = format!("{} failed", self.failed);
+
+		let file = self
+			.file
+			.as_deref()
+			.map(|file| match Path::new(file).file_name() {
+				Some(name) => name.to_string_lossy().into_owned(),
+				None => file.to_owned(),
+			})
+			.unwrap_or_default();
+
+		// Account for the ", " and " — " separators, and leave the last
+		// column empty so that the line doesn't wrap.
+		let used = prefix.chars().count()
+			+ passed.chars().count()
+			+ failed.chars().count()
+			+ 6;
+
+		let file: String = match width {
+			Some(width) => {
+				file.chars().take(width.saturating_sub(used)).collect()
+			},
+			None => file,
+		};
+
+		let failed = match self.failed {
+			0 => failed.normal(),
+			_ => failed.red(),
+		};
+
+		format!("{}{}, {failed} — {file}", prefix.bold(), passed.green())
+	}
+}
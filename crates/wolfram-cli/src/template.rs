@@ -0,0 +1,320 @@
+//! Templates used by `$ wolfram-cli paclet new` to create new paclets.
+//!
+//! A template is a directory of files that are copied into the new paclet
+//! directory. Occurrences of the following variables in the contents and
+//! paths of the files are replaced with their values for the new paclet:
+//!
+//! * `{{PacletName}}` — the full name of the paclet, e.g. `MyPublisher/MyPaclet`
+//! * `{{Publisher}}` — the publisher of the paclet, or an empty string
+//! * `{{BaseName}}` — the base name of the paclet, e.g. `MyPaclet`
+//! * `{{Context}}` — the primary context of the paclet, e.g. `MyPublisher`MyPaclet``
+//! * `{{SnakeCaseName}}` — the base name in snake case, e.g. `my_paclet`
+//! * `{{KebabCaseName}}` — the base name in kebab case, e.g. `my-paclet`
+//!
+//! A `.template` suffix is removed from the names of template files. This
+//! allows templates to contain files like `Cargo.toml` that would otherwise be
+//! interpreted by other tools.
+
+use std::path::{Path, PathBuf};
+
+/// Suffix removed from the names of template files.
+const TEMPLATE_SUFFIX: &str = ".template";
+
+macro_rules! template_files {
+	($dir:literal: $($path:literal),* $(,)?) => {
+		&[$(
+			($path, include_str!(concat!("../templates/", $dir, "/", $path))),
+		)*]
+	};
+}
+
+/// Paclet containing a Wolfram Language package.
+const LIBRARY_TEMPLATE: &[(&str, &str)] = template_files!("library":
+	"PacletInfo.wl",
+	"Kernel/{{BaseName}}.wl",
+	"Tests/{{BaseName}}.wlt",
+);
+
+/// Paclet with a `WolframCLI` extension that adds a subcommand to
+/// `wolfram-cli`.
+const CLI_TEMPLATE: &[(&str, &str)] = template_files!("cli":
+	"PacletInfo.wl",
+	"Kernel/{{BaseName}}.wl",
+	"Tests/{{BaseName}}.wlt",
+);
+
+/// Paclet with a Rust LibraryLink library crate.
+const RUST_TEMPLATE: &[(&str, &str)] = template_files!("rust":
+	"PacletInfo.wl",
+	"Kernel/{{BaseName}}.wl",
+	"Tests/{{BaseName}}.wlt",
+	"Cargo.toml.template",
+	"src/lib.rs",
+	".gitignore.template",
+);
+
+pub enum Template {
+	/// A template built in to `wolfram-cli`.
+	BuiltIn(&'static [(&'static str, &'static str)]),
+	/// A template directory provided by the user.
+	Directory(PathBuf),
+}
+
+/// Values of the variables substituted into template files.
+pub struct Variables {
+	values: Vec<(&'static str, String)>,
+}
+
+impl Template {
+	/// Get the built-in template called `name`, or the template in the
+	/// directory `name`.
+	pub fn from_name(name: &str) -> Result<Template, String> {
+		match name {
+			"library" => Ok(Template::BuiltIn(LIBRARY_TEMPLATE)),
+			"cli" => Ok(Template::BuiltIn(CLI_TEMPLATE)),
+			"rust" => Ok(Template::BuiltIn(RUST_TEMPLATE)),
+			_ if Path::new(name).is_dir() => {
+				Ok(Template::Directory(PathBuf::from(name)))
+			},
+			_ => Err(format!(
+				"unknown paclet template: {name:?}: expected 'library', 'cli', 'rust', or the path to a template directory"
+			)),
+		}
+	}
+
+	/// Create the files of this template in `dest`, which must not already
+	/// exist.
+	pub fn instantiate(
+		&self,
+		dest: &Path,
+		variables: &Variables,
+	) -> Result<(), String> {
+		if dest.exists() {
+			return Err(format!(
+				"paclet directory already exists: {}",
+				dest.display()
+			));
+		}
+
+		let files: Vec<(String, Vec<u8>)> = match self {
+			Template::BuiltIn(files) => files
+				.iter()
+				.map(|(path, contents)| {
+					(path.to_string(), contents.as_bytes().to_vec())
+				})
+				.collect(),
+			Template::Directory(dir) => read_template_dir(dir, dir)?,
+		};
+
+		for (path, contents) in files {
+			let path = variables.substitute(&path);
+			let path = path.strip_suffix(TEMPLATE_SUFFIX).unwrap_or(&path);
+			let path = dest.join(path);
+
+			// Substitute variables only in text files.
+			let contents = match String::from_utf8(contents) {
+				Ok(text) => variables.substitute(&text).into_bytes(),
+				Err(err) => err.into_bytes(),
+			};
+
+			if let Some(parent) = path.parent() {
+				std::fs::create_dir_all(parent).map_err(|err| {
+					format!(
+						"error creating directory {}: {err}",
+						parent.display()
+					)
+				})?;
+			}
+
+			std::fs::write(&path, contents).map_err(|err| {
+				format!("error writing file {}: {err}", path.display())
+			})?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Read the files in the template directory `dir`, returning their paths
+/// relative to `root` and their contents.
+fn read_template_dir(
+	root: &Path,
+	dir: &Path,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+	let read_error =
+		|path: &Path, err| format!("error reading {}: {err}", path.display());
+
+	let mut files = Vec::new();
+
+	let entries = std::fs::read_dir(dir).map_err(|err| read_error(dir, err))?;
+
+	for entry in entries {
+		let path = entry.map_err(|err| read_error(dir, err))?.path();
+
+		if path.file_name() == Some(".git".as_ref()) {
+			continue;
+		}
+
+		if path.is_dir() {
+			files.extend(read_template_dir(root, &path)?);
+			continue;
+		}
+
+		let relative = path.strip_prefix(root).map_err(|_| {
+			format!(
+				"template file {} is not in the template directory {}",
+				path.display(),
+				root.display()
+			)
+		})?;
+
+		let relative = match relative.to_str() {
+			Some(relative) => relative.replace('\\', "/"),
+			None => {
+				return Err(format!(
+					"template file path is not valid UTF-8: {}",
+					path.display()
+				))
+			},
+		};
+
+		let contents =
+			std::fs::read(&path).map_err(|err| read_error(&path, err))?;
+
+		files.push((relative, contents));
+	}
+
+	Ok(files)
+}
+
+impl Variables {
+	/// Get the variable values for a paclet with the specified publisher and
+	/// base name.
+	pub fn new(publisher: Option<&str>, base: &str) -> Variables {
+		let (name, context) = match publisher {
+			Some(publisher) => (
+				format!("{publisher}/{base}"),
+				format!("{publisher}`{base}`"),
+			),
+			None => (base.to_owned(), format!("{base}`")),
+		};
+
+		Variables {
+			values: vec![
+				("PacletName", name),
+				("Publisher", publisher.unwrap_or_default().to_owned()),
+				("BaseName", base.to_owned()),
+				("Context", context),
+				("SnakeCaseName", separate_words(base, '_')),
+				("KebabCaseName", separate_words(base, '-')),
+			],
+		}
+	}
+
+	/// Replace each `{{Variable}}` in `text` with the value of the variable.
+	fn substitute(&self, text: &str) -> String {
+		let mut text = text.to_owned();
+
+		for (variable, value) in &self.values {
+			text = text.replace(&format!("{{{{{variable}}}}}"), value);
+		}
+
+		text
+	}
+}
+
+/// Convert a `CamelCase` name into lowercase words separated by `separator`.
+fn separate_words(name: &str, separator: char) -> String {
+	let mut result = String::new();
+	let mut previous: Option<char> = None;
+
+	for char in name.chars() {
+		if !char.is_alphanumeric() {
+			result.push(separator);
+		} else {
+			if char.is_uppercase()
+				&& previous.is_some_and(|c| c.is_lowercase() || c.is_numeric())
+			{
+				result.push(separator);
+			}
+
+			result.extend(char.to_lowercase());
+		}
+
+		previous = Some(char);
+	}
+
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::test_util::TempDir;
+
+	#[test]
+	fn test_separate_words() {
+		assert_eq!(separate_words("MyPaclet", '_'), "my_paclet");
+		assert_eq!(separate_words("MyPaclet", '-'), "my-paclet");
+		assert_eq!(separate_words("Paclet", '-'), "paclet");
+		assert_eq!(separate_words("Base64Encode", '-'), "base64-encode");
+		assert_eq!(separate_words("My Paclet", '_'), "my_paclet");
+		// Runs of capitals are kept together.
+		assert_eq!(separate_words("HTTPClient", '-'), "httpclient");
+	}
+
+	#[test]
+	fn test_variables() {
+		let variables = Variables::new(Some("Publisher"), "MyPaclet");
+
+		assert_eq!(
+			variables.substitute(
+				"{{PacletName}} {{Publisher}} {{BaseName}} {{Context}}"
+			),
+			"Publisher/MyPaclet Publisher MyPaclet Publisher`MyPaclet`"
+		);
+		assert_eq!(
+			variables.substitute("{{SnakeCaseName}} {{KebabCaseName}}"),
+			"my_paclet my-paclet"
+		);
+
+		let variables = Variables::new(None, "MyPaclet");
+
+		assert_eq!(
+			variables.substitute("{{PacletName}}|{{Publisher}}|{{Context}}"),
+			"MyPaclet||MyPaclet`"
+		);
+	}
+
+	#[test]
+	fn test_substitute() {
+		let variables = Variables::new(None, "MyPaclet");
+
+		// Each occurrence is replaced.
+		assert_eq!(
+			variables.substitute("{{BaseName}}.wl, {{BaseName}}.wlt"),
+			"MyPaclet.wl, MyPaclet.wlt"
+		);
+		// Unknown variables and single braces are left unchanged.
+		assert_eq!(
+			variables.substitute("{{Unknown}} {BaseName} <|a -> {1}|>"),
+			"{{Unknown}} {BaseName} <|a -> {1}|>"
+		);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_read_template_dir_invalid_file_name() {
+		use std::os::unix::ffi::OsStrExt;
+
+		let dir = TempDir::new("template-invalid-file-name");
+		dir.write("PacletInfo.wl", "");
+
+		let name = std::ffi::OsStr::from_bytes(b"Kernel\xFF.wl");
+		std::fs::write(dir.path().join(name), "").unwrap();
+
+		let err = read_template_dir(dir.path(), dir.path()).unwrap_err();
+		assert!(err.starts_with("template file path is not valid UTF-8"));
+	}
+}
@@ -0,0 +1,48 @@
+//! Utilities shared by unit tests.
+
+use std::path::{Path, PathBuf};
+
+/// Temporary directory that is deleted when dropped.
+pub struct TempDir {
+	path: PathBuf,
+}
+
+impl TempDir {
+	/// Create an empty temporary directory whose name includes `name`, which
+	/// should be unique among the tests.
+	pub fn new(name: &str) -> TempDir {
+		let path = std::env::temp_dir()
+			.join(format!("wolfram-cli-test-{name}-{}", std::process::id()));
+
+		if path.exists() {
+			std::fs::remove_dir_all(&path).unwrap();
+		}
+
+		std::fs::create_dir_all(&path).unwrap();
+
+		TempDir {
+			path: path.canonicalize().unwrap(),
+		}
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// Write `contents` to the file at `relative` in this directory, creating
+	/// any parent directories.
+	pub fn write(&self, relative: &str, contents: &str) -> PathBuf {
+		let file = self.path.join(relative);
+
+		std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+		std::fs::write(&file, contents).unwrap();
+
+		file
+	}
+}
+
+impl Drop for TempDir {
+	fn drop(&mut self) {
+		let _ = std::fs::remove_dir_all(&self.path);
+	}
+}
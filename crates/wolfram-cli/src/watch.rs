@@ -0,0 +1,115 @@
+//! Utilities for re-running a command when files on disk change.
+
+use std::{
+	collections::BTreeSet,
+	path::{Path, PathBuf},
+	sync::mpsc::{self, Receiver, RecvTimeoutError},
+	time::Duration,
+};
+
+use notify::{Event, EventKind, RecursiveMode, Watcher as _};
+
+/// How long to wait for further file system events after the first change is
+/// detected.
+///
+/// Editors commonly save a file as several separate write, rename, and
+/// metadata events; waiting until the events settle down means a single save
+/// triggers a single re-run.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+pub struct Watcher {
+	/// Dropping the watcher stops the delivery of events.
+	_watcher: notify::RecommendedWatcher,
+	events: Receiver<notify::Result<Event>>,
+	/// Changes to paths inside these directories are ignored.
+	ignored: Vec<PathBuf>,
+}
+
+impl Watcher {
+	/// Recursively watch each of `paths` for changes, ignoring any changes
+	/// inside the `ignored` directories.
+	pub fn new(paths: &[&Path], ignored: Vec<PathBuf>) -> Watcher {
+		let (sender, events) = mpsc::channel();
+
+		let mut watcher = notify::recommended_watcher(sender)
+			.expect("unable to create file system watcher");
+
+		for path in paths {
+			watcher
+				.watch(path, RecursiveMode::Recursive)
+				.unwrap_or_else(|err| {
+					panic!("unable to watch path {}: {err}", path.display())
+				});
+		}
+
+		let ignored = ignored
+			.into_iter()
+			.map(|dir| dir.canonicalize().unwrap_or(dir))
+			.collect();
+
+		Watcher {
+			_watcher: watcher,
+			events,
+			ignored,
+		}
+	}
+
+	/// Block until at least one watched file changes, and return the set of
+	/// files that changed.
+	pub fn wait_for_changes(&self) -> BTreeSet<PathBuf> {
+		let mut changed = BTreeSet::new();
+
+		// Wait indefinitely for the first relevant change.
+		while changed.is_empty() {
+			let event = self
+				.events
+				.recv()
+				.expect("file system watcher unexpectedly disconnected");
+
+			self.record(event, &mut changed);
+		}
+
+		// Collect any further changes that arrive within the debounce period.
+		loop {
+			match self.events.recv_timeout(DEBOUNCE) {
+				Ok(event) => self.record(event, &mut changed),
+				Err(RecvTimeoutError::Timeout) => break,
+				Err(RecvTimeoutError::Disconnected) => {
+					panic!("file system watcher unexpectedly disconnected")
+				},
+			}
+		}
+
+		changed
+	}
+
+	fn record(
+		&self,
+		event: notify::Result<Event>,
+		changed: &mut BTreeSet<PathBuf>,
+	) {
+		let event = match event {
+			Ok(event) => event,
+			Err(err) => {
+				eprintln!("warning: error watching files for changes: {err}");
+				return;
+			},
+		};
+
+		// Ignore events that don't indicate that a file was changed.
+		if let EventKind::Access(_) | EventKind::Any | EventKind::Other =
+			event.kind
+		{
+			return;
+		}
+
+		for path in event.paths {
+			let is_ignored =
+				self.ignored.iter().any(|dir| path.starts_with(dir));
+
+			if !is_ignored {
+				changed.insert(path);
+			}
+		}
+	}
+}
@@ -0,0 +1,383 @@
+//! Workspaces of paclets that are built, tested, and documented together.
+//!
+//! A workspace is a directory containing a `wolfram-cli-workspace.toml`
+//! manifest file that lists the paclet directories that are members of the
+//! workspace, relative to the manifest file:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["Core", "Paclets/*"]
+//! ```
+//!
+//! A member ending in `/*` includes every paclet directory directly inside
+//! that directory.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::paclet_info::PacletInfo;
+
+/// Name of the workspace manifest file.
+pub const MANIFEST_FILE: &str = "wolfram-cli-workspace.toml";
+
+pub struct Workspace {
+	/// Directory containing the workspace manifest file.
+	pub root: PathBuf,
+	/// Members of the workspace, ordered so that each member comes after any
+	/// other members it depends on.
+	pub members: Vec<Member>,
+}
+
+pub struct Member {
+	/// Absolute path of the paclet directory.
+	pub dir: PathBuf,
+	pub info: PacletInfo,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+	workspace: ManifestWorkspace,
+}
+
+#[derive(Deserialize)]
+struct ManifestWorkspace {
+	members: Vec<String>,
+}
+
+impl Workspace {
+	/// Find the workspace manifest file in the current directory or one of
+	/// its ancestors, and load the workspace it describes.
+	pub fn discover() -> Result<Workspace, String> {
+		let current_dir = std::env::current_dir()
+			.expect("unable to get process current working directory");
+
+		let root = current_dir
+			.ancestors()
+			.find(|dir| dir.join(MANIFEST_FILE).is_file())
+			.ok_or_else(|| {
+				format!(
+					"no {MANIFEST_FILE} file was found in {} or any parent directory",
+					current_dir.display()
+				)
+			})?;
+
+		Workspace::load(root)
+	}
+
+	/// Load the workspace whose manifest file is in `root`.
+	pub fn load(root: &Path) -> Result<Workspace, String> {
+		let manifest_file = root.join(MANIFEST_FILE);
+
+		let contents =
+			std::fs::read_to_string(&manifest_file).map_err(|err| {
+				format!("error reading {}: {err}", manifest_file.display())
+			})?;
+
+		let manifest: Manifest = toml::from_str(&contents).map_err(|err| {
+			format!("error parsing {}: {err}", manifest_file.display())
+		})?;
+
+		let mut members = Vec::new();
+
+		for pattern in &manifest.workspace.members {
+			for dir in member_dirs(root, pattern)? {
+				let dir = dir.canonicalize().map_err(|err| {
+					format!("error reading {}: {err}", dir.display())
+				})?;
+
+				if members.iter().any(|member: &Member| member.dir == dir) {
+					continue;
+				}
+
+				let info = PacletInfo::read(&dir)?;
+
+				members.push(Member { dir, info });
+			}
+		}
+
+		Ok(Workspace {
+			root: root.to_path_buf(),
+			members: dependency_order(members)?,
+		})
+	}
+
+	/// Paclet directories of all members of the workspace.
+	pub fn member_dirs(&self) -> Vec<PathBuf> {
+		self.members
+			.iter()
+			.map(|member| member.dir.clone())
+			.collect()
+	}
+}
+
+/// Get the paclet directories named by the member `pattern`.
+fn member_dirs(root: &Path, pattern: &str) -> Result<Vec<PathBuf>, String> {
+	let Some(parent) = pattern.strip_suffix("/*") else {
+		let dir = root.join(pattern);
+
+		if !dir.is_dir() {
+			return Err(format!(
+				"workspace member {pattern:?} is not a directory: {}",
+				dir.display()
+			));
+		}
+
+		return Ok(vec![dir]);
+	};
+
+	let parent = root.join(parent);
+
+	let entries = std::fs::read_dir(&parent).map_err(|err| {
+		format!(
+			"error reading workspace members {pattern:?} in {}: {err}",
+			parent.display()
+		)
+	})?;
+
+	let mut dirs: Vec<PathBuf> = entries
+		.filter_map(|entry| Some(entry.ok()?.path()))
+		.filter(|path| {
+			path.join("PacletInfo.wl").is_file()
+				|| path.join("PacletInfo.m").is_file()
+		})
+		.collect();
+
+	dirs.sort();
+
+	Ok(dirs)
+}
+
+/// Sort `members` so that each member comes after the members listed in its
+/// `Dependencies`, keeping the manifest order otherwise.
+///
+/// Returns an error if two members are the same paclet, since dependencies
+/// on that paclet would be ambiguous.
+fn dependency_order(members: Vec<Member>) -> Result<Vec<Member>, String> {
+	for (index, member) in members.iter().enumerate() {
+		if let Some(other) = members[..index]
+			.iter()
+			.find(|other| other.info.name == member.info.name)
+		{
+			return Err(format!(
+				"workspace members {} and {} are both paclet {:?}",
+				other.dir.display(),
+				member.dir.display(),
+				member.info.name
+			));
+		}
+	}
+
+	#[derive(Clone, Copy, PartialEq)]
+	enum State {
+		Unvisited,
+		Visiting,
+		Visited,
+	}
+
+	fn visit(
+		index: usize,
+		members: &[Member],
+		states: &mut [State],
+		order: &mut Vec<usize>,
+		path: &mut Vec<usize>,
+	) -> Result<(), String> {
+		match states[index] {
+			State::Visited => return Ok(()),
+			State::Visiting => {
+				let start = path.iter().position(|&i| i == index).unwrap();
+				let cycle: Vec<&str> = path[start..]
+					.iter()
+					.chain([&index])
+					.map(|&i| members[i].info.name.as_str())
+					.collect();
+
+				return Err(format!(
+					"workspace members have a dependency cycle: {}",
+					cycle.join(" -> ")
+				));
+			},
+			State::Unvisited => (),
+		}
+
+		states[index] = State::Visiting;
+		path.push(index);
+
+		for dependency in &members[index].info.dependencies {
+			if let Some(dependency) = members
+				.iter()
+				.position(|member| member.info.name == dependency.name)
+			{
+				visit(dependency, members, states, order, path)?;
+			}
+		}
+
+		path.pop();
+		states[index] = State::Visited;
+		order.push(index);
+
+		Ok(())
+	}
+
+	let mut states = vec![State::Unvisited; members.len()];
+	let mut order = Vec::new();
+
+	for index in 0..members.len() {
+		visit(index, &members, &mut states, &mut order, &mut Vec::new())?;
+	}
+
+	let mut members: Vec<Option<Member>> =
+		members.into_iter().map(Some).collect();
+
+	Ok(order
+		.into_iter()
+		.map(|index| members[index].take().unwrap())
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::test_util::TempDir;
+
+	fn paclet_info(name: &str, dependencies: &[&str]) -> String {
+		let dependencies: Vec<String> = dependencies
+			.iter()
+			.map(|dependency| format!("{dependency:?}"))
+			.collect();
+
+		format!(
+			"PacletObject[<| \"Name\" -> {name:?}, \"Dependencies\" -> {{{}}} |>]",
+			dependencies.join(", ")
+		)
+	}
+
+	fn member(name: &str, dependencies: &[&str]) -> Member {
+		Member {
+			dir: PathBuf::from(name),
+			info: PacletInfo::from_text(&paclet_info(name, dependencies))
+				.unwrap(),
+		}
+	}
+
+	fn names(members: &[Member]) -> Vec<&str> {
+		members
+			.iter()
+			.map(|member| member.info.name.as_str())
+			.collect()
+	}
+
+	#[test]
+	fn test_dependency_order() {
+		let members = dependency_order(vec![
+			member("App", &["Core", "Utils"]),
+			member("Utils", &["Core"]),
+			member("Core", &["Wolfram/ErrorTools"]),
+		])
+		.unwrap();
+
+		assert_eq!(names(&members), ["Core", "Utils", "App"]);
+	}
+
+	#[test]
+	fn test_dependency_order_is_stable() {
+		let members = dependency_order(vec![
+			member("C", &[]),
+			member("A", &[]),
+			member("B", &["A"]),
+			member("D", &[]),
+		])
+		.unwrap();
+
+		assert_eq!(names(&members), ["C", "A", "B", "D"]);
+	}
+
+	#[test]
+	fn test_dependency_cycle() {
+		let result = dependency_order(vec![
+			member("Other", &[]),
+			member("A", &["B"]),
+			member("B", &["C"]),
+			member("C", &["A"]),
+		]);
+
+		assert_eq!(
+			result.err().unwrap(),
+			"workspace members have a dependency cycle: A -> B -> C -> A"
+		);
+	}
+
+	#[test]
+	fn test_duplicate_paclet_names() {
+		let result = dependency_order(vec![
+			member("A", &[]),
+			Member {
+				dir: PathBuf::from("Copy"),
+				..member("A", &[])
+			},
+		]);
+
+		assert_eq!(
+			result.err().unwrap(),
+			"workspace members A and Copy are both paclet \"A\""
+		);
+	}
+
+	#[test]
+	fn test_member_dirs() {
+		let root = TempDir::new("workspace-member-dirs");
+
+		root.write("Paclets/B/PacletInfo.wl", &paclet_info("B", &[]));
+		root.write("Paclets/A/PacletInfo.m", "Paclet[Name -> \"A\"]");
+		root.write("Paclets/Notes/README.md", "");
+		root.write("Paclets/file.txt", "");
+
+		assert_eq!(
+			member_dirs(root.path(), "Paclets/*").unwrap(),
+			[root.path().join("Paclets/A"), root.path().join("Paclets/B")]
+		);
+
+		assert_eq!(
+			member_dirs(root.path(), "Paclets/B").unwrap(),
+			[root.path().join("Paclets/B")]
+		);
+
+		assert!(member_dirs(root.path(), "Missing").is_err());
+		assert!(member_dirs(root.path(), "Missing/*").is_err());
+	}
+
+	#[test]
+	fn test_load() {
+		let root = TempDir::new("workspace-load");
+
+		root.write(
+			MANIFEST_FILE,
+			"[workspace]\nmembers = [\"App\", \"Paclets/*\", \"Paclets/Core\"]\n",
+		);
+		root.write("App/PacletInfo.wl", &paclet_info("App", &["Core"]));
+		root.write("Paclets/Core/PacletInfo.wl", &paclet_info("Core", &[]));
+
+		let workspace = Workspace::load(root.path()).unwrap();
+
+		// Paclets/Core is listed twice, but is only a member once.
+		assert_eq!(names(&workspace.members), ["Core", "App"]);
+		assert_eq!(
+			workspace.member_dirs(),
+			[root.path().join("Paclets/Core"), root.path().join("App")]
+		);
+	}
+
+	#[test]
+	fn test_load_duplicate_paclet_names() {
+		let root = TempDir::new("workspace-duplicate-names");
+
+		root.write(MANIFEST_FILE, "[workspace]\nmembers = [\"A\", \"B\"]\n");
+		root.write("A/PacletInfo.wl", &paclet_info("Same", &[]));
+		root.write("B/PacletInfo.wl", &paclet_info("Same", &[]));
+
+		let err = Workspace::load(root.path()).err().unwrap();
+
+		assert!(err.contains("are both paclet \"Same\""), "{err}");
+	}
+}
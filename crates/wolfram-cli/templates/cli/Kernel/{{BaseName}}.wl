@@ -0,0 +1,23 @@
+BeginPackage["{{Context}}"]
+
+Handle{{BaseName}}Subcommand::usage = "Handle{{BaseName}}Subcommand[args] handles the command `$ wolfram-cli {{KebabCaseName}}`."
+
+Begin["`Private`"]
+
+Needs["ConnorGray`WolframCLI`" -> "CLI`"];
+
+Handle{{BaseName}}Subcommand[
+	cliArgs: {___?StringQ}
+] := Replace[cliArgs, {
+	{_, "{{KebabCaseName}}", name_?StringQ} :> (
+		Print["Hello, ", CLI`TerminalStyle[name, Bold], "!"];
+	),
+	other_ :> Failure["{{BaseName}}", <|
+		"MessageTemplate" -> "unexpected CLI arguments: ``",
+		"MessageParameters" -> {InputForm[other]}
+	|>]
+}]
+
+End[] (* End `Private` *)
+
+EndPackage[]
@@ -0,0 +1,18 @@
+PacletObject[<|
+	"Name" -> "{{PacletName}}",
+	"Description" -> "",
+	"Version" -> "0.0.1",
+	"WolframVersion" -> "13.2+",
+	"PrimaryContext" -> "{{Context}}",
+	"Extensions" -> {
+		{"Kernel",
+			"Root" -> "Kernel",
+			"Context" -> "{{Context}}"
+		},
+		{"WolframCLI",
+			"Subcommand" -> "{{KebabCaseName}}",
+			"HandlerFunction" -> "{{Context}}Handle{{BaseName}}Subcommand"
+		},
+		{"Tests", "Root" -> "Tests"}
+	}
+|>]
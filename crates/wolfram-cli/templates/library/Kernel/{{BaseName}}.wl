@@ -0,0 +1,11 @@
+BeginPackage["{{Context}}"]
+
+(* Declare the public symbols of the paclet here, with a usage message. *)
+
+Begin["`Private`"]
+
+(* Define the public and private symbols of the paclet here. *)
+
+End[] (* End `Private` *)
+
+EndPackage[]
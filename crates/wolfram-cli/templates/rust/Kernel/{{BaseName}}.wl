@@ -0,0 +1,15 @@
+BeginPackage["{{Context}}"]
+
+AddIntegers::usage = "AddIntegers[a, b] adds the integers a and b using a function implemented in Rust."
+
+Begin["`Private`"]
+
+(* The library is built from the Cargo crate in this paclet by
+   `wolfram-cli paclet build`. *)
+$functions = LibraryFunctionLoad["lib{{SnakeCaseName}}", "load_library_functions", LinkObject, LinkObject][]
+
+AddIntegers[a_Integer, b_Integer] := $functions["add_integers"][a, b]
+
+End[] (* End `Private` *)
+
+EndPackage[]
@@ -0,0 +1,15 @@
+PacletObject[<|
+	"Name" -> "{{PacletName}}",
+	"Description" -> "",
+	"Version" -> "0.0.1",
+	"WolframVersion" -> "13.2+",
+	"PrimaryContext" -> "{{Context}}",
+	"Extensions" -> {
+		{"Kernel",
+			"Root" -> "Kernel",
+			"Context" -> "{{Context}}"
+		},
+		{"LibraryResources"},
+		{"Tests", "Root" -> "Tests"}
+	}
+|>]
@@ -0,0 +1,16 @@
+use wolfram_library_link::{self as wll, expr::Expr};
+
+/// Returns an association of the library functions exported by this library,
+/// for loading with `LibraryFunctionLoad`.
+#[wll::export(wstp, hidden)]
+fn load_library_functions(args: Vec<Expr>) -> Expr {
+	assert!(args.is_empty());
+	wll::exported_library_functions_association(Some(
+		"lib{{SnakeCaseName}}".into(),
+	))
+}
+
+#[wll::export]
+fn add_integers(a: i64, b: i64) -> i64 {
+	a + b
+}